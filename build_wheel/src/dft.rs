@@ -6,7 +6,7 @@ use feos_dft::python::*;
 use feos_dft::solvation::*;
 use feos_dft::*;
 use feos_pets::python::*;
-use feos_pets::PetsFunctional;
+use feos_pets::{HardSphereDiameter, IdealGasModel, PetsFunctional, PetsOptions};
 use numpy::*;
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
@@ -21,20 +21,55 @@ use std::rc::Rc;
 /// ----------
 /// parameters: PetsParameters
 ///     The set of PeTS parameters.
+/// feynman_hibbs : bool, optional
+///     Apply the Feynman-Hibbs quantum correction. Defaults to False.
+/// tail_correction : bool, optional
+///     Truncate the potential at 2.5 sigma without shifting and add the
+///     long-range correction. Defaults to False.
+/// hs_diameter : HardSphereDiameter, optional
+///     Model of the hard-sphere diameter. Defaults to HardSphereDiameter.Correlation.
+/// ideal_gas : IdealGasModel, optional
+///     Model of the ideal gas contribution. Defaults to IdealGasModel.Automatic,
+///     the ideal gas records if available and the QSPR model otherwise.
 ///
 /// Returns
 /// -------
 /// PetsFunctional
 #[pyclass(name = "PetsFunctional", unsendable)]
-#[pyo3(text_signature = "(parameters)")]
+#[pyo3(
+    text_signature = "(parameters, feynman_hibbs, tail_correction, hs_diameter, ideal_gas)"
+)]
 #[derive(Clone)]
 pub struct PyPetsFunctional(pub Rc<DFT<PetsFunctional>>);
 
 #[pymethods]
 impl PyPetsFunctional {
     #[new]
-    fn new(parameters: PyPetsParameters) -> Self {
-        Self(Rc::new(PetsFunctional::new(parameters.0)))
+    #[args(
+        feynman_hibbs = "false",
+        tail_correction = "false",
+        hs_diameter = "HardSphereDiameter::Correlation",
+        ideal_gas = "IdealGasModel::Automatic"
+    )]
+    fn new(
+        parameters: PyPetsParameters,
+        feynman_hibbs: bool,
+        tail_correction: bool,
+        hs_diameter: HardSphereDiameter,
+        ideal_gas: IdealGasModel,
+    ) -> PyResult<Self> {
+        let options = PetsOptions {
+            feynman_hibbs,
+            tail_correction,
+            hs_diameter,
+            ideal_gas,
+            ..Default::default()
+        };
+        Ok(Self(Rc::new(PetsFunctional::with_options(
+            parameters.0,
+            FMTVersion::WhiteBear,
+            options,
+        )?)))
     }
 
     /// PeTS Helmholtz energy functional without simplifications
@@ -46,14 +81,50 @@ impl PyPetsFunctional {
     ///     The set of PeTS parameters.
     /// fmt_version: FMTVersion
     ///     Specify the FMT term.
+    /// feynman_hibbs : bool, optional
+    ///     Apply the Feynman-Hibbs quantum correction. Defaults to False.
+    /// tail_correction : bool, optional
+    ///     Truncate the potential at 2.5 sigma without shifting and add the
+    ///     long-range correction. Defaults to False.
+    /// hs_diameter : HardSphereDiameter, optional
+    ///     Model of the hard-sphere diameter. Defaults to HardSphereDiameter.Correlation.
+    /// ideal_gas : IdealGasModel, optional
+    ///     Model of the ideal gas contribution. Defaults to IdealGasModel.Automatic,
+    ///     the ideal gas records if available and the QSPR model otherwise.
     ///
     /// Returns
     /// -------
     /// PetsFunctional
     #[staticmethod]
-    #[pyo3(text_signature = "(parameters, fmt_version)")]
-    fn new_full(parameters: PyPetsParameters, fmt_version: FMTVersion) -> Self {
-        Self(Rc::new(PetsFunctional::new_full(parameters.0, fmt_version)))
+    #[args(
+        feynman_hibbs = "false",
+        tail_correction = "false",
+        hs_diameter = "HardSphereDiameter::Correlation",
+        ideal_gas = "IdealGasModel::Automatic"
+    )]
+    #[pyo3(
+        text_signature = "(parameters, fmt_version, feynman_hibbs, tail_correction, hs_diameter, ideal_gas)"
+    )]
+    fn new_full(
+        parameters: PyPetsParameters,
+        fmt_version: FMTVersion,
+        feynman_hibbs: bool,
+        tail_correction: bool,
+        hs_diameter: HardSphereDiameter,
+        ideal_gas: IdealGasModel,
+    ) -> PyResult<Self> {
+        let options = PetsOptions {
+            feynman_hibbs,
+            tail_correction,
+            hs_diameter,
+            ideal_gas,
+            ..Default::default()
+        };
+        Ok(Self(Rc::new(PetsFunctional::with_options(
+            parameters.0,
+            fmt_version,
+            options,
+        )?)))
     }
 }
 
@@ -1,6 +1,7 @@
 use feos_core::*;
-use feos_pets::{Pets, PetsOptions};
-use feos_pets::python::PyPetsParameters;
+use feos_pets::python::{PyPetsParameters, PyReducedUnits};
+use feos_pets::reduced::ReducedState;
+use feos_pets::{HardSphereDiameter, HardSphereModel, IdealGasModel, Pets, PetsOptions};
 use numpy::convert::ToPyArray;
 use numpy::{PyArray1, PyArray2};
 use pyo3::exceptions::PyValueError;
@@ -18,6 +19,18 @@ use std::rc::Rc;
 ///     The parameters of the PeTS equation of state to use.
 /// max_eta : float, optional
 ///     Maximum packing fraction. Defaults to 0.5.
+/// feynman_hibbs : bool, optional
+///     Apply the Feynman-Hibbs quantum correction. Defaults to False.
+/// tail_correction : bool, optional
+///     Truncate the potential at 2.5 sigma without shifting and add the
+///     long-range correction. Defaults to False.
+/// hard_sphere_model : HardSphereModel, optional
+///     Hard-sphere mixture reference. Defaults to HardSphereModel.BMCSL.
+/// hs_diameter : HardSphereDiameter, optional
+///     Model of the hard-sphere diameter. Defaults to HardSphereDiameter.Correlation.
+/// ideal_gas : IdealGasModel, optional
+///     Model of the ideal gas contribution. Defaults to IdealGasModel.Automatic,
+///     the ideal gas records if available and the QSPR model otherwise.
 ///
 /// Returns
 /// -------
@@ -25,17 +38,62 @@ use std::rc::Rc;
 ///     The PeTS equation of state that can be used to compute thermodynamic
 ///     states.
 #[pyclass(name = "Pets", unsendable)]
-#[pyo3(text_signature = "(parameters, max_eta)")]
+#[pyo3(
+    text_signature = "(parameters, max_eta, feynman_hibbs, tail_correction, hard_sphere_model, hs_diameter, ideal_gas)"
+)]
 #[derive(Clone)]
 pub struct PyPets(pub Rc<Pets>);
 
 #[pymethods]
 impl PyPets {
     #[new]
-    #[args(max_eta = "0.5")]
-    fn new(parameters: PyPetsParameters, max_eta: f64) -> Self {
-        let options = PetsOptions { max_eta };
-        Self(Rc::new(Pets::with_options(parameters.0.clone(), options)))
+    #[args(
+        max_eta = "0.5",
+        feynman_hibbs = "false",
+        tail_correction = "false",
+        hard_sphere_model = "HardSphereModel::BMCSL",
+        hs_diameter = "HardSphereDiameter::Correlation",
+        ideal_gas = "IdealGasModel::Automatic"
+    )]
+    fn new(
+        parameters: PyPetsParameters,
+        max_eta: f64,
+        feynman_hibbs: bool,
+        tail_correction: bool,
+        hard_sphere_model: HardSphereModel,
+        hs_diameter: HardSphereDiameter,
+        ideal_gas: IdealGasModel,
+    ) -> PyResult<Self> {
+        let options = PetsOptions {
+            max_eta,
+            feynman_hibbs,
+            tail_correction,
+            hard_sphere_model,
+            hs_diameter,
+            ideal_gas,
+        };
+        Ok(Self(Rc::new(Pets::with_options(
+            parameters.0.clone(),
+            options,
+        )?)))
+    }
+
+    /// Calculate the self-diffusion coefficients of all components in a state.
+    ///
+    /// For components that are not present in the state, the diffusion
+    /// coefficients at infinite dilution are returned.
+    ///
+    /// Parameters
+    /// ----------
+    /// state : State
+    ///     The thermodynamic state.
+    ///
+    /// Returns
+    /// -------
+    /// SIArray1
+    #[pyo3(text_signature = "($self, state)")]
+    fn self_diffusion_coefficients(&self, state: &PyState) -> PyResult<PySIArray1> {
+        Ok(self.0.self_diffusion_coefficients(&state.0)?.into())
     }
 }
 
@@ -47,11 +105,91 @@ impl_state_molarweight!(Pets, PyPets);
 impl_state_entropy_scaling!(Pets, PyPets);
 impl_phase_equilibrium!(Pets, PyPets);
 
+/// Thermodynamic state of a pure fluid specified and evaluated in reduced units.
+///
+/// Parameters
+/// ----------
+/// eos : Pets
+///     The PeTS equation of state.
+/// units : ReducedUnits
+///     The reference quantities of the reduced units.
+/// temperature : float
+///     The reduced temperature.
+/// density : float
+///     The reduced density.
+///
+/// Returns
+/// -------
+/// ReducedState
+#[pyclass(name = "ReducedState", unsendable)]
+#[pyo3(text_signature = "(eos, units, temperature, density)")]
+pub struct PyReducedState(ReducedState<Pets>);
+
+#[pymethods]
+impl PyReducedState {
+    #[new]
+    fn new(eos: PyPets, units: PyReducedUnits, temperature: f64, density: f64) -> PyResult<Self> {
+        Ok(Self(ReducedState::new(
+            &eos.0,
+            units.0,
+            temperature,
+            density,
+        )?))
+    }
+
+    /// The state in SI units.
+    #[getter]
+    fn get_state(&self) -> PyState {
+        PyState(self.0.state.clone())
+    }
+
+    #[getter]
+    fn get_temperature(&self) -> f64 {
+        self.0.temperature()
+    }
+
+    #[getter]
+    fn get_density(&self) -> f64 {
+        self.0.density()
+    }
+
+    #[args(contributions = "Contributions::Total")]
+    #[pyo3(text_signature = "($self, contributions)")]
+    fn pressure(&self, contributions: Contributions) -> f64 {
+        self.0.pressure(contributions)
+    }
+
+    #[args(contributions = "Contributions::Total")]
+    #[pyo3(text_signature = "($self, contributions)")]
+    fn internal_energy(&self, contributions: Contributions) -> f64 {
+        self.0.internal_energy(contributions)
+    }
+
+    #[args(contributions = "Contributions::Total")]
+    #[pyo3(text_signature = "($self, contributions)")]
+    fn chemical_potential(&self, contributions: Contributions) -> f64 {
+        self.0.chemical_potential(contributions)
+    }
+
+    fn viscosity(&self) -> PyResult<f64> {
+        Ok(self.0.viscosity()?)
+    }
+
+    fn diffusion(&self) -> PyResult<f64> {
+        Ok(self.0.diffusion()?)
+    }
+
+    fn thermal_conductivity(&self) -> PyResult<f64> {
+        Ok(self.0.thermal_conductivity()?)
+    }
+}
+
 #[pymodule]
 pub fn eos(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
     m.add_class::<PyPets>()?;
     m.add_class::<PyState>()?;
     m.add_class::<PyPhaseDiagram>()?;
     m.add_class::<PyPhaseEquilibrium>()?;
+    m.add_class::<PyReducedState>()?;
     Ok(())
 }
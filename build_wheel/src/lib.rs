@@ -1,15 +1,21 @@
 use feos_core::python::joback::PyJobackRecord;
 use feos_core::python::parameter::*;
 use feos_core::{Contributions, Verbosity};
+use feos_pets::fitting::BinaryVleObjective;
 use feos_pets::python::*;
+use feos_pets::{
+    CombiningRule, HardSphereDiameter, HardSphereModel, IdealGasModel, TransportProperty,
+};
 use pyo3::prelude::*;
-use pyo3::wrap_pymodule;
+use pyo3::{wrap_pyfunction, wrap_pymodule};
 use quantity::python::__PYO3_PYMODULE_DEF_QUANTITY;
 
 mod dft;
 mod eos;
+mod lj;
 use dft::__PYO3_PYMODULE_DEF_DFT;
 use eos::__PYO3_PYMODULE_DEF_EOS;
+use lj::__PYO3_PYMODULE_DEF_LJ;
 
 #[pymodule]
 pub fn feos_pets(py: Python<'_>, m: &PyModule) -> PyResult<()> {
@@ -20,12 +26,32 @@ pub fn feos_pets(py: Python<'_>, m: &PyModule) -> PyResult<()> {
     m.add_class::<PyJobackRecord>()?;
 
     m.add_class::<PyPetsRecord>()?;
+    m.add_class::<PyPetsIdealGasRecord>()?;
     m.add_class::<PyPureRecord>()?;
     m.add_class::<PyBinaryRecord>()?;
     m.add_class::<PyPetsParameters>()?;
+    m.add_class::<CombiningRule>()?;
+    m.add_class::<HardSphereModel>()?;
+    m.add_class::<HardSphereDiameter>()?;
+    m.add_class::<IdealGasModel>()?;
+    m.add_class::<PyReducedUnits>()?;
+
+    m.add_class::<PyVaporPressure>()?;
+    m.add_class::<PyLiquidDensity>()?;
+    m.add_class::<PyPureFitResult>()?;
+    m.add_function(wrap_pyfunction!(py_fit_pure, m)?)?;
+    m.add_class::<PyBinaryVle>()?;
+    m.add_class::<PyBinaryFitResult>()?;
+    m.add_class::<BinaryVleObjective>()?;
+    m.add_function(wrap_pyfunction!(py_fit_binary, m)?)?;
+    m.add_class::<TransportProperty>()?;
+    m.add_class::<PyTransportData>()?;
+    m.add_class::<PyTransportFitResult>()?;
+    m.add_function(wrap_pyfunction!(py_fit_entropy_scaling, m)?)?;
 
     m.add_wrapped(wrap_pymodule!(eos))?;
     m.add_wrapped(wrap_pymodule!(dft))?;
+    m.add_wrapped(wrap_pymodule!(lj))?;
     m.add_wrapped(wrap_pymodule!(quantity))?;
 
     py.run(
@@ -33,6 +59,7 @@ pub fn feos_pets(py: Python<'_>, m: &PyModule) -> PyResult<()> {
 import sys
 sys.modules['feos_pets.eos'] = eos
 sys.modules['feos_pets.dft'] = dft
+sys.modules['feos_pets.lj'] = lj
 quantity.SINumber.__module__ = 'feos_pets.si'
 quantity.SIArray1.__module__ = 'feos_pets.si'
 quantity.SIArray2.__module__ = 'feos_pets.si'
@@ -0,0 +1,63 @@
+use feos_core::*;
+use feos_pets::python::PyPetsParameters;
+use feos_pets::{IdealGasModel, LennardJones};
+use numpy::convert::ToPyArray;
+use numpy::{PyArray1, PyArray2};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use quantity::python::*;
+use quantity::si::*;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Initialize the equation of state of the full Lennard-Jones fluid
+/// (Johnson, Zollweg and Gubbins, 1993).
+///
+/// Only `sigma`, `epsilon_k` and the binary interaction parameters are used;
+/// mixtures are described with the van der Waals one-fluid theory.
+///
+/// Parameters
+/// ----------
+/// parameters : PetsParameters
+///     The parameters of the PeTS equation of state to use.
+/// ideal_gas : IdealGasModel, optional
+///     Model of the ideal gas contribution. Defaults to IdealGasModel.Automatic,
+///     the ideal gas records if available and the QSPR model otherwise.
+///
+/// Returns
+/// -------
+/// LennardJones
+///     The Lennard-Jones equation of state that can be used to compute
+///     thermodynamic states.
+#[pyclass(name = "LennardJones", unsendable)]
+#[pyo3(text_signature = "(parameters, ideal_gas)")]
+#[derive(Clone)]
+pub struct PyLennardJones(pub Rc<LennardJones>);
+
+#[pymethods]
+impl PyLennardJones {
+    #[new]
+    #[args(ideal_gas = "IdealGasModel::Automatic")]
+    fn new(parameters: PyPetsParameters, ideal_gas: IdealGasModel) -> PyResult<Self> {
+        Ok(Self(Rc::new(LennardJones::with_ideal_gas(
+            parameters.0,
+            ideal_gas,
+        )?)))
+    }
+}
+
+impl_equation_of_state!(PyLennardJones);
+impl_virial_coefficients!(PyLennardJones);
+
+impl_state!(LennardJones, PyLennardJones);
+impl_state_molarweight!(LennardJones, PyLennardJones);
+impl_phase_equilibrium!(LennardJones, PyLennardJones);
+
+#[pymodule]
+pub fn lj(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
+    m.add_class::<PyLennardJones>()?;
+    m.add_class::<PyState>()?;
+    m.add_class::<PyPhaseDiagram>()?;
+    m.add_class::<PyPhaseEquilibrium>()?;
+    Ok(())
+}
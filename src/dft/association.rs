@@ -0,0 +1,192 @@
+use crate::eos::association::association_helmholtz_energy_density;
+use crate::eos::PetsOptions;
+use crate::parameters::PetsParameters;
+use feos_core::EosError;
+use feos_dft::{
+    FunctionalContributionDual, WeightFunction, WeightFunctionInfo, WeightFunctionShape,
+};
+use ndarray::*;
+use num_dual::DualNum;
+use std::fmt;
+use std::rc::Rc;
+
+/// Association functional of Yu and Wu (2002).
+///
+/// The densities and the contact value are evaluated with the
+/// FMT weighted densities and the inhomogeneity factor `1 - n2v² / n2²`.
+#[derive(Clone)]
+pub struct AssociationFunctional {
+    parameters: Rc<PetsParameters>,
+    options: PetsOptions,
+}
+
+impl AssociationFunctional {
+    pub fn new(parameters: Rc<PetsParameters>, options: PetsOptions) -> Self {
+        Self {
+            parameters,
+            options,
+        }
+    }
+}
+
+impl<N: DualNum<f64> + ScalarOperand> FunctionalContributionDual<N> for AssociationFunctional {
+    fn weight_functions(&self, temperature: N) -> WeightFunctionInfo<N> {
+        let p = &self.parameters;
+        let r = p.hs_diameter(temperature, &self.options) * N::from(0.5);
+        let m = p.m.mapv(N::from);
+        WeightFunctionInfo::new(Array1::from_shape_fn(r.len(), |i| i), false)
+            .add(
+                WeightFunction::new_scaled(r.clone(), WeightFunctionShape::Delta),
+                false,
+            )
+            .add(
+                WeightFunction {
+                    prefactor: m.clone(),
+                    kernel_radius: r.clone(),
+                    shape: WeightFunctionShape::Delta,
+                },
+                true,
+            )
+            .add(
+                WeightFunction {
+                    prefactor: m.clone(),
+                    kernel_radius: r.clone(),
+                    shape: WeightFunctionShape::Theta,
+                },
+                true,
+            )
+            .add(
+                WeightFunction {
+                    prefactor: m,
+                    kernel_radius: r,
+                    shape: WeightFunctionShape::DeltaVec,
+                },
+                true,
+            )
+    }
+
+    fn calculate_helmholtz_energy_density(
+        &self,
+        temperature: N,
+        weighted_densities: ArrayView2<N>,
+    ) -> Result<Array1<N>, EosError> {
+        let p = &self.parameters;
+        let n = p.m.len();
+        let d = p.hs_diameter(temperature, &self.options);
+        let dim = weighted_densities.shape()[0] - n - 2;
+
+        // weighted densities
+        let rho0 = weighted_densities.slice_axis(Axis(0), Slice::new(0, Some(n as isize), 1));
+        let n2 = weighted_densities.index_axis(Axis(0), n);
+        let n3 = weighted_densities.index_axis(Axis(0), n + 1);
+        let n2v = weighted_densities.slice_axis(Axis(0), Slice::new(n as isize + 2, None, 1));
+
+        // inhomogeneity factor
+        let n2v2 = (0..dim).fold(Array::zeros(n2.raw_dim()), |acc: Array1<N>, i| {
+            let n2v_i = n2v.index_axis(Axis(0), i);
+            acc + &n2v_i * &n2v_i
+        });
+        let xi = Zip::from(&n2v2).and(&n2).map_collect(|&n2v2, &n2| {
+            if n2.re() == 0.0 {
+                N::one()
+            } else {
+                -n2v2 / (n2 * n2) + 1.0
+            }
+        });
+
+        let rho = &rho0 * &xi;
+        let zeta2 = &n2 * &xi / 6.0;
+        let mut phi = Array::zeros(n3.raw_dim());
+        for (k, phi) in phi.iter_mut().enumerate() {
+            *phi = association_helmholtz_energy_density(
+                p,
+                &self.options,
+                temperature,
+                &d,
+                rho.index_axis(Axis(1), k),
+                zeta2[k],
+                n3[k],
+            )?;
+        }
+        Ok(phi)
+    }
+}
+
+impl fmt::Display for AssociationFunctional {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Association functional")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dft::PetsFunctional;
+    use crate::eos::Pets;
+    use crate::parameters::utils::{argon_krypton_parameters, argon_parameters, map_model_records};
+    use approx::assert_relative_eq;
+    use feos_core::{Contributions, PhaseEquilibrium, SolverOptions, State};
+    use feos_dft::interface::PlanarInterface;
+    use ndarray::arr1;
+    use quantity::si::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn bulk_consistency() {
+        let t = 200.0 * KELVIN;
+        let v = 1e-4 * METER.powi(3);
+        for (parameters, n) in [
+            (
+                map_model_records(&argon_parameters(), |_, r| {
+                    r.with_association(0.03, 1000.0, 1.0, 1.0)
+                }),
+                arr1(&[1.0]) * MOL,
+            ),
+            (
+                map_model_records(&argon_krypton_parameters(), |i, r| {
+                    r.with_segments([1.0, 1.5][i]).with_association(
+                        [0.03, 0.01][i],
+                        [1000.0, 800.0][i],
+                        1.0,
+                        [1.0, 2.0][i],
+                    )
+                }),
+                arr1(&[0.3, 0.7]) * MOL,
+            ),
+        ] {
+            let eos = Rc::new(Pets::new(parameters.clone()));
+            let func = Rc::new(PetsFunctional::new(parameters));
+            let s_eos = State::new_nvt(&eos, t, v, &n).unwrap();
+            let s_func = State::new_nvt(&func, t, v, &n).unwrap();
+            assert_relative_eq!(
+                s_eos.pressure(Contributions::Total),
+                s_func.pressure(Contributions::Total),
+                max_relative = 1e-10
+            );
+            assert_relative_eq!(
+                s_eos.chemical_potential(Contributions::ResidualNvt),
+                s_func.chemical_potential(Contributions::ResidualNvt),
+                max_relative = 1e-10
+            );
+        }
+    }
+
+    #[test]
+    fn surface_tension() {
+        let t = 100.0 * KELVIN;
+        let gamma = |parameters| {
+            let func = Rc::new(PetsFunctional::new(parameters));
+            let vle = PhaseEquilibrium::pure(&func, t, None, SolverOptions::default()).unwrap();
+            PlanarInterface::from_tanh(&vle, 512, 100.0 * ANGSTROM, 500.0 * KELVIN)
+                .unwrap()
+                .solve(None)
+                .unwrap()
+                .surface_tension
+                .unwrap()
+        };
+        let argon = gamma(argon_parameters());
+        let associating = gamma(map_model_records(&argon_parameters(), |_, r| {
+            r.with_association(0.03, 500.0, 1.0, 1.0)
+        }));
+        assert!(associating > argon);
+    }
+}
@@ -0,0 +1,134 @@
+use crate::eos::cutoff::{tail_correction, PETS_CUTOFF};
+use crate::eos::PetsOptions;
+use crate::parameters::PetsParameters;
+use feos_core::EosError;
+use feos_dft::{
+    FunctionalContributionDual, WeightFunction, WeightFunctionInfo, WeightFunctionShape,
+};
+use ndarray::*;
+use num_dual::DualNum;
+use std::f64::consts::PI;
+use std::fmt;
+use std::rc::Rc;
+
+/// Mean-field long-range correction of PeTS.
+///
+/// The correction of the equation of state is evaluated with the densities
+/// averaged over a sphere with the radius of the cutoff.
+#[derive(Clone)]
+pub struct CutoffCorrectionFunctional {
+    parameters: Rc<PetsParameters>,
+    options: PetsOptions,
+}
+
+impl CutoffCorrectionFunctional {
+    pub fn new(parameters: Rc<PetsParameters>, options: PetsOptions) -> Self {
+        Self {
+            parameters,
+            options,
+        }
+    }
+}
+
+impl<N: DualNum<f64> + ScalarOperand> FunctionalContributionDual<N> for CutoffCorrectionFunctional {
+    fn weight_functions(&self, _: N) -> WeightFunctionInfo<N> {
+        let p = &self.parameters;
+        WeightFunctionInfo::new(Array1::from_shape_fn(p.sigma.len(), |i| i), false).add(
+            WeightFunction::new_scaled(
+                p.sigma.mapv(|s| N::from(s * PETS_CUTOFF)),
+                WeightFunctionShape::Theta,
+            ),
+            false,
+        )
+    }
+
+    fn calculate_helmholtz_energy_density(
+        &self,
+        temperature: N,
+        density: ArrayView2<N>,
+    ) -> Result<Array1<N>, EosError> {
+        let p = &self.parameters;
+        let n = p.sigma.len();
+        let d = p.hs_diameter(temperature, &self.options);
+        let (sigma_ij, epsilon_k_ij) =
+            p.effective_cross_parameters(temperature, self.options.feynman_hibbs);
+
+        let mut phi: Array1<N> = Array::zeros(density.raw_dim().remove_axis(Axis(0)));
+        for i in 0..n {
+            for j in 0..n {
+                let d_ij = (d[i] + d[j]) * 0.5 / sigma_ij[(i, j)];
+                let f = epsilon_k_ij[(i, j)] / temperature
+                    * sigma_ij[(i, j)].powi(3)
+                    * (p.m[i] * p.m[j])
+                    * tail_correction(d_ij, p.lambda_r_ij(i, j))
+                    * 2.0
+                    * PI;
+                phi = phi
+                    + (&density.index_axis(Axis(0), i) * &density.index_axis(Axis(0), j))
+                        .mapv(|x| x * f);
+            }
+        }
+        Ok(phi)
+    }
+}
+
+impl fmt::Display for CutoffCorrectionFunctional {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Long-range correction (rc = {} sigma)", PETS_CUTOFF)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dft::PetsFunctional;
+    use crate::eos::mie::u_mie;
+    use crate::eos::{Pets, PetsOptions};
+    use crate::parameters::utils::{argon_krypton_parameters, argon_parameters};
+    use approx::assert_relative_eq;
+    use feos_core::{Contributions, State};
+    use feos_dft::fundamental_measure_theory::FMTVersion;
+    use feos_dft::solvation::PairPotential;
+    use ndarray::arr1;
+    use quantity::si::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn bulk_consistency() {
+        let t = 120.0 * KELVIN;
+        let v = 1e-4 * METER.powi(3);
+        for (parameters, n) in [
+            (argon_parameters(), arr1(&[1.0]) * MOL),
+            (argon_krypton_parameters(), arr1(&[0.3, 0.7]) * MOL),
+        ] {
+            let options = PetsOptions {
+                tail_correction: true,
+                ..Default::default()
+            };
+            let eos = Rc::new(Pets::with_options(parameters.clone(), options).unwrap());
+            let func = Rc::new(
+                PetsFunctional::with_options(parameters, FMTVersion::WhiteBear, options).unwrap(),
+            );
+            let s_eos = State::new_nvt(&eos, t, v, &n).unwrap();
+            let s_func = State::new_nvt(&func, t, v, &n).unwrap();
+            assert_relative_eq!(
+                s_eos.pressure(Contributions::Total),
+                s_func.pressure(Contributions::Total),
+                max_relative = 1e-10
+            );
+        }
+    }
+
+    #[test]
+    fn pair_potential_tail_correction() {
+        let options = PetsOptions {
+            tail_correction: true,
+            ..Default::default()
+        };
+        let func = PetsFunctional::with_options(argon_parameters(), FMTVersion::WhiteBear, options)
+            .unwrap();
+        let sigma = 3.405;
+        let u = func.pair_potential(&arr1(&[0.99999 * 2.5 * sigma, 1.00001 * 2.5 * sigma]));
+        assert_relative_eq!(u[(0, 0)], 119.8 * u_mie(2.5, 12.0), max_relative = 1e-3);
+        assert_eq!(u[(0, 1)], 0.0);
+    }
+}
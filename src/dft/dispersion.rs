@@ -1,4 +1,5 @@
-use crate::eos::dispersion::{A, B};
+use crate::eos::dispersion::{c1, A, B};
+use crate::eos::PetsOptions;
 use crate::parameters::PetsParameters;
 use feos_core::EosError;
 use feos_dft::{
@@ -11,29 +12,32 @@ use std::fmt;
 use std::rc::Rc;
 
 /// psi Parameter for DFT (Heier2018)
-const PSI_DFT: f64 = 1.21;
+pub(super) const PSI_DFT: f64 = 1.21;
 /// psi Parameter for pDGT (not adjusted, yet)
-const PSI_PDGT: f64 = 1.21;
+pub(super) const PSI_PDGT: f64 = 1.21;
 
 #[derive(Clone)]
 pub struct AttractiveFunctional {
     parameters: Rc<PetsParameters>,
+    options: PetsOptions,
 }
 
 impl AttractiveFunctional {
-    pub fn new(parameters: Rc<PetsParameters>) -> Self {
+    pub fn new(parameters: Rc<PetsParameters>, options: PetsOptions) -> Self {
         Self {
-            parameters: parameters.clone(),
+            parameters,
+            options,
         }
     }
 }
 
-fn att_weight_functions<N: DualNum<f64> + ScalarOperand>(
+pub(super) fn att_weight_functions<N: DualNum<f64> + ScalarOperand>(
     p: &PetsParameters,
+    options: &PetsOptions,
     psi: f64,
     temperature: N,
 ) -> WeightFunctionInfo<N> {
-    let d = p.hs_diameter(temperature);
+    let d = p.hs_diameter(temperature, options);
     WeightFunctionInfo::new(Array1::from_shape_fn(d.len(), |i| i), false).add(
         WeightFunction::new_scaled(d * psi, WeightFunctionShape::Theta),
         false,
@@ -42,11 +46,11 @@ fn att_weight_functions<N: DualNum<f64> + ScalarOperand>(
 
 impl<N: DualNum<f64> + ScalarOperand> FunctionalContributionDual<N> for AttractiveFunctional {
     fn weight_functions(&self, temperature: N) -> WeightFunctionInfo<N> {
-        att_weight_functions(&self.parameters, PSI_DFT, temperature)
+        att_weight_functions(&self.parameters, &self.options, PSI_DFT, temperature)
     }
 
     fn weight_functions_pdgt(&self, temperature: N) -> WeightFunctionInfo<N> {
-        att_weight_functions(&self.parameters, PSI_PDGT, temperature)
+        att_weight_functions(&self.parameters, &self.options, PSI_PDGT, temperature)
     }
 
     fn calculate_helmholtz_energy_density(
@@ -59,30 +63,56 @@ impl<N: DualNum<f64> + ScalarOperand> FunctionalContributionDual<N> for Attracti
         let n = p.sigma.len();
 
         // temperature dependent segment radius
-        let r = p.hs_diameter(temperature) * 0.5;
+        let r = p.hs_diameter(temperature, &self.options) * 0.5;
 
         // packing fraction
         let eta = density
             .outer_iter()
-            .zip((&r * &r * &r * 4.0 * FRAC_PI_3).into_iter())
+            .zip(&r * &r * &r * &p.m.mapv(N::from) * 4.0 * FRAC_PI_3)
             .fold(
                 Array::zeros(density.raw_dim().remove_axis(Axis(0))),
                 |acc: Array1<N>, (rho, r3)| acc + &rho * r3,
             );
 
+        // mean segment number
+        let m = if p.has_chains() {
+            let rho = density.sum_axis(Axis(0));
+            let rho_m = density
+                .outer_iter()
+                .zip(p.m.iter())
+                .fold(Array::zeros(rho.raw_dim()), |acc: Array1<N>, (rho, &m)| {
+                    acc + &rho * m
+                });
+            // the mean segment number is irrelevant where the density vanishes
+            Zip::from(&rho_m).and(&rho).map_collect(|&rho_m, &rho| {
+                if rho.re() > 0.0 {
+                    rho_m / rho
+                } else {
+                    N::one()
+                }
+            })
+        } else {
+            Array::ones(eta.raw_dim())
+        };
+
+        // temperature dependent cross parameters
+        let (sigma_ij, epsilon_k_ij) =
+            p.effective_cross_parameters(temperature, self.options.feynman_hibbs);
+        let (s1, s2) = p.dispersion_scaling();
+
         // mixture densities, crosswise interactions of all segments on all chains
         let mut rho1mix: Array1<N> = Array::zeros(eta.raw_dim());
         let mut rho2mix: Array1<N> = Array::zeros(eta.raw_dim());
         for i in 0..n {
             for j in 0..n {
-                let eps_ij_t = temperature.recip() * p.epsilon_k_ij[(i, j)];
-                let sigma_ij_3 = p.sigma_ij[(i, j)].powi(3);
+                let eps_ij_t = epsilon_k_ij[(i, j)] / temperature;
+                let sigma_ij_3 = sigma_ij[(i, j)].powi(3) * (p.m[i] * p.m[j]);
                 rho1mix = rho1mix
                     + (&density.index_axis(Axis(0), i) * &density.index_axis(Axis(0), j))
-                        .mapv(|x| x * (eps_ij_t * sigma_ij_3));
+                        .mapv(|x| x * (eps_ij_t * sigma_ij_3 * s1[(i, j)]));
                 rho2mix = rho2mix
                     + (&density.index_axis(Axis(0), i) * &density.index_axis(Axis(0), j))
-                        .mapv(|x| x * (eps_ij_t * eps_ij_t * sigma_ij_3));
+                        .mapv(|x| x * (eps_ij_t * eps_ij_t * sigma_ij_3 * s2[(i, j)]));
             }
         }
 
@@ -95,11 +125,10 @@ impl<N: DualNum<f64> + ScalarOperand> FunctionalContributionDual<N> for Attracti
             i2 = i2 + &eta_i * B[i];
             eta_i = &eta_i * &eta;
         }
-        let c1 =
-            eta.mapv(|eta| ((eta * 8.0 - eta.powi(2) * 2.0) / (eta - 1.0).powi(4) + 1.0).recip());
+        let c1 = Zip::from(&eta).and(&m).map_collect(|&eta, &m| c1(eta, m));
 
         // Helmholtz energy density
-        Ok((-rho1mix * i1 * 2.0 - rho2mix * c1 * i2) * PI)
+        Ok((-rho1mix * i1 * 2.0 - rho2mix * m * c1 * i2) * PI)
     }
 }
 
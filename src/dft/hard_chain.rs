@@ -0,0 +1,178 @@
+use crate::eos::hard_sphere::hs_contact_value;
+use crate::eos::PetsOptions;
+use crate::parameters::PetsParameters;
+use feos_core::EosError;
+use feos_dft::{
+    FunctionalContributionDual, WeightFunction, WeightFunctionInfo, WeightFunctionShape,
+};
+use ndarray::*;
+use num_dual::DualNum;
+use std::fmt;
+use std::rc::Rc;
+
+/// Hard-chain functional (TPT1) of Tripathi and Chapman.
+///
+/// The ideal chain contribution is added by the DFT itself,
+/// this functional only accounts for the bonding.
+#[derive(Clone)]
+pub struct ChainFunctional {
+    parameters: Rc<PetsParameters>,
+    options: PetsOptions,
+}
+
+impl ChainFunctional {
+    pub fn new(parameters: Rc<PetsParameters>, options: PetsOptions) -> Self {
+        Self {
+            parameters,
+            options,
+        }
+    }
+}
+
+impl<N: DualNum<f64> + ScalarOperand> FunctionalContributionDual<N> for ChainFunctional {
+    fn weight_functions(&self, temperature: N) -> WeightFunctionInfo<N> {
+        let p = &self.parameters;
+        let d = p.hs_diameter(temperature, &self.options);
+        WeightFunctionInfo::new(Array1::from_shape_fn(d.len(), |i| i), true)
+            .add(
+                WeightFunction::new_scaled(d.clone(), WeightFunctionShape::Delta),
+                false,
+            )
+            .add(
+                WeightFunction {
+                    prefactor: p.m.mapv(|m| N::from(m / 24.0)),
+                    kernel_radius: d.clone(),
+                    shape: WeightFunctionShape::Delta,
+                },
+                true,
+            )
+            .add(
+                WeightFunction {
+                    prefactor: p.m.mapv(|m| N::from(m / 8.0)),
+                    kernel_radius: d,
+                    shape: WeightFunctionShape::Theta,
+                },
+                true,
+            )
+    }
+
+    fn calculate_helmholtz_energy_density(
+        &self,
+        temperature: N,
+        weighted_densities: ArrayView2<N>,
+    ) -> Result<Array1<N>, EosError> {
+        let p = &self.parameters;
+        let n = p.m.len();
+        let d = p.hs_diameter(temperature, &self.options);
+
+        // weighted densities
+        let rho = weighted_densities.slice_axis(Axis(0), Slice::new(0, Some(n as isize), 1));
+        let lambda =
+            weighted_densities.slice_axis(Axis(0), Slice::new(n as isize, Some(2 * n as isize), 1));
+        let zeta2 = weighted_densities.index_axis(Axis(0), 2 * n);
+        let zeta3 = weighted_densities.index_axis(Axis(0), 2 * n + 1);
+
+        let mut phi: Array1<N> = Array::zeros(zeta2.raw_dim());
+        for i in 0..n {
+            if p.m[i] == 1.0 {
+                continue;
+            }
+            let y = Zip::from(&zeta2)
+                .and(&zeta3)
+                .map_collect(|&z2, &z3| hs_contact_value(z2, z3, d[i] * 0.5));
+            phi = phi
+                - (y * lambda.index_axis(Axis(0), i)).mapv(|x| x.ln() - 1.0)
+                    * rho.index_axis(Axis(0), i)
+                    * (p.m[i] - 1.0);
+        }
+        Ok(phi)
+    }
+}
+
+impl fmt::Display for ChainFunctional {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Hard chain functional")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dft::PetsFunctional;
+    use crate::eos::Pets;
+    use crate::parameters::utils::{argon_krypton_parameters, argon_parameters, map_model_records};
+    use approx::assert_relative_eq;
+    use feos_core::{Contributions, PhaseEquilibrium, SolverOptions, State};
+    use feos_dft::interface::PlanarInterface;
+    use ndarray::arr1;
+    use quantity::si::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn bulk_consistency() {
+        let t = 200.0 * KELVIN;
+        let v = 1e-4 * METER.powi(3);
+        for (parameters, n) in [
+            (
+                map_model_records(&argon_parameters(), |_, r| r.with_segments(2.5)),
+                arr1(&[1.0]) * MOL,
+            ),
+            (
+                map_model_records(&argon_krypton_parameters(), |i, r| {
+                    r.with_segments([1.0, 1.8][i])
+                }),
+                arr1(&[0.3, 0.7]) * MOL,
+            ),
+        ] {
+            let eos = Rc::new(Pets::new(parameters.clone()));
+            let func = Rc::new(PetsFunctional::new(parameters));
+            let s_eos = State::new_nvt(&eos, t, v, &n).unwrap();
+            let s_func = State::new_nvt(&func, t, v, &n).unwrap();
+            assert_relative_eq!(
+                s_eos.pressure(Contributions::Total),
+                s_func.pressure(Contributions::Total),
+                max_relative = 1e-10
+            );
+            assert_relative_eq!(
+                s_eos.chemical_potential(Contributions::ResidualNvt),
+                s_func.chemical_potential(Contributions::ResidualNvt),
+                max_relative = 1e-10
+            );
+        }
+    }
+
+    #[test]
+    fn surface_tension_dimer() {
+        let func = Rc::new(PetsFunctional::new(map_model_records(
+            &argon_parameters(),
+            |_, r| r.with_segments(2.0),
+        )));
+        let t = 150.0 * KELVIN;
+        let vle = PhaseEquilibrium::pure(&func, t, None, SolverOptions::default()).unwrap();
+        let interface = PlanarInterface::from_tanh(&vle, 512, 100.0 * ANGSTROM, 500.0 * KELVIN)
+            .unwrap()
+            .solve(None)
+            .unwrap();
+
+        // the density profile connects the coexisting densities
+        let rho = interface
+            .profile
+            .density
+            .to_reduced(MOL / METER.powi(3))
+            .unwrap();
+        assert_relative_eq!(
+            rho[(0, 0)],
+            vle.liquid()
+                .density
+                .to_reduced(MOL / METER.powi(3))
+                .unwrap(),
+            max_relative = 1e-4
+        );
+        assert_relative_eq!(
+            rho[(0, 511)],
+            vle.vapor().density.to_reduced(MOL / METER.powi(3)).unwrap(),
+            max_relative = 1e-4
+        );
+        let gamma = interface.surface_tension.unwrap();
+        assert!(gamma > 0.0 * NEWTON / METER);
+    }
+}
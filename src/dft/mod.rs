@@ -1,21 +1,31 @@
+use crate::eos::cutoff::PETS_CUTOFF;
+use crate::eos::ideal_gas::IdealGasContributions;
+use crate::eos::mie::u_mie;
 use crate::eos::PetsOptions;
 use crate::parameters::PetsParameters;
+use association::AssociationFunctional;
+use cutoff::CutoffCorrectionFunctional;
 use dispersion::AttractiveFunctional;
-use feos_core::joback::Joback;
 use feos_core::parameter::Parameter;
-use feos_core::{IdealGasContribution, MolarWeight};
+use feos_core::{EosResult, IdealGasContribution, MolarWeight};
 use feos_dft::adsorption::FluidParameters;
 use feos_dft::fundamental_measure_theory::{FMTContribution, FMTProperties, FMTVersion};
 use feos_dft::solvation::PairPotential;
 use feos_dft::{FunctionalContribution, HelmholtzEnergyFunctional, MoleculeShape, DFT};
+use hard_chain::ChainFunctional;
 use ndarray::{Array, Array1, Array2};
 use num_dual::DualNum;
+use polar::{DipoleFunctional, DipoleQuadrupoleFunctional, QuadrupoleFunctional};
 use pure_pets_functional::*;
 use quantity::si::*;
 use std::f64::consts::FRAC_PI_6;
 use std::rc::Rc;
 
+mod association;
+mod cutoff;
 mod dispersion;
+mod hard_chain;
+mod polar;
 mod pure_pets_functional;
 
 pub struct PetsFunctional {
@@ -23,62 +33,101 @@ pub struct PetsFunctional {
     fmt_version: FMTVersion,
     options: PetsOptions,
     contributions: Vec<Box<dyn FunctionalContribution>>,
-    joback: Joback,
+    ideal_gas: IdealGasContributions,
 }
 
 impl PetsFunctional {
     pub fn new(parameters: Rc<PetsParameters>) -> DFT<Self> {
-        Self::with_options(parameters, FMTVersion::WhiteBear, PetsOptions::default())
+        Self::new_full(parameters, FMTVersion::WhiteBear)
     }
 
     #[allow(non_snake_case)]
     pub fn new_full(parameters: Rc<PetsParameters>, fmt_Version: FMTVersion) -> DFT<Self> {
-        Self::with_options(parameters, fmt_Version, PetsOptions::default())
+        // the default ideal gas model is available for all parameters
+        Self::with_options(parameters, fmt_Version, PetsOptions::default()).unwrap()
     }
 
     pub fn with_options(
         parameters: Rc<PetsParameters>,
         fmt_version: FMTVersion,
         pets_options: PetsOptions,
-    ) -> DFT<Self> {
+    ) -> EosResult<DFT<Self>> {
         let mut contributions: Vec<Box<dyn FunctionalContribution>> = Vec::with_capacity(2);
 
         if matches!(
             fmt_version,
             FMTVersion::WhiteBear | FMTVersion::AntiSymWhiteBear
         ) && parameters.sigma.len() == 1
+            && !parameters.has_chains()
         // Pure substance or mixture
         {
             // Hard-sphere contribution pure substance
-            let fmt = PureFMTFunctional::new(parameters.clone(), fmt_version);
+            let fmt = PureFMTFunctional::new(parameters.clone(), pets_options, fmt_version);
             contributions.push(Box::new(fmt.clone()));
 
             // Dispersion contribution pure substance
-            let att = PureAttFunctional::new(parameters.clone());
+            let att = PureAttFunctional::new(parameters.clone(), pets_options);
             contributions.push(Box::new(att.clone()));
         } else {
             // Hard-sphere contribution mixtures
-            let hs = FMTContribution::new(&parameters, fmt_version);
+            let properties = Rc::new(PetsFMTProperties {
+                parameters: parameters.clone(),
+                options: pets_options,
+            });
+            let hs = FMTContribution::new(&properties, fmt_version);
             contributions.push(Box::new(hs.clone()));
 
+            // Hard-chain contribution
+            if parameters.has_chains() {
+                let chain = ChainFunctional::new(parameters.clone(), pets_options);
+                contributions.push(Box::new(chain));
+            }
+
             // Dispersion contribution mixtures
-            let att = AttractiveFunctional::new(parameters.clone());
+            let att = AttractiveFunctional::new(parameters.clone(), pets_options);
             contributions.push(Box::new(att.clone()));
         }
 
-        let joback = match &parameters.joback_records {
-            Some(joback_records) => Joback::new(joback_records.clone()),
-            None => Joback::default(parameters.sigma.len()),
-        };
+        // Dipole-dipole contribution
+        if parameters.has_dipoles() {
+            let dipole = DipoleFunctional::new(parameters.clone(), pets_options);
+            contributions.push(Box::new(dipole));
+        }
+
+        // Quadrupole-quadrupole contribution
+        if parameters.has_quadrupoles() {
+            let quadrupole = QuadrupoleFunctional::new(parameters.clone(), pets_options);
+            contributions.push(Box::new(quadrupole));
+        }
+
+        // Dipole-quadrupole contribution
+        if parameters.has_dipoles() && parameters.has_quadrupoles() {
+            let dq = DipoleQuadrupoleFunctional::new(parameters.clone(), pets_options);
+            contributions.push(Box::new(dq));
+        }
+
+        // Association contribution
+        if parameters.has_association() {
+            let assoc = AssociationFunctional::new(parameters.clone(), pets_options);
+            contributions.push(Box::new(assoc));
+        }
 
-        Self {
+        // Long-range correction
+        if pets_options.tail_correction {
+            let cutoff = CutoffCorrectionFunctional::new(parameters.clone(), pets_options);
+            contributions.push(Box::new(cutoff));
+        }
+
+        let ideal_gas = IdealGasContributions::new(&parameters, pets_options.ideal_gas)?;
+
+        Ok(Self {
             parameters: parameters.clone(),
             fmt_version,
             options: pets_options,
             contributions,
-            joback,
+            ideal_gas,
         }
-        .into()
+        .into())
     }
 }
 
@@ -89,15 +138,21 @@ impl HelmholtzEnergyFunctional for PetsFunctional {
             self.fmt_version,
             self.options,
         )
+        .expect("The subset contains the ideal gas records of the components.")
     }
 
-    fn molecule_shape(&self) -> MoleculeShape {
-        MoleculeShape::Spherical(self.parameters.sigma.len())
+    fn molecule_shape(&self) -> MoleculeShape<'_> {
+        if self.parameters.has_chains() {
+            MoleculeShape::NonSpherical(&self.parameters.m)
+        } else {
+            MoleculeShape::Spherical(self.parameters.sigma.len())
+        }
     }
 
     fn compute_max_density(&self, moles: &Array1<f64>) -> f64 {
         self.options.max_eta * moles.sum()
-            / (FRAC_PI_6 * self.parameters.sigma.mapv(|v| v.powi(3)) * moles).sum()
+            / (FRAC_PI_6 * &self.parameters.m * self.parameters.sigma.mapv(|v| v.powi(3)) * moles)
+                .sum()
     }
 
     fn contributions(&self) -> &[Box<dyn FunctionalContribution>] {
@@ -105,7 +160,7 @@ impl HelmholtzEnergyFunctional for PetsFunctional {
     }
 
     fn ideal_gas(&self) -> &dyn IdealGasContribution {
-        &self.joback
+        self.ideal_gas.as_dyn()
     }
 }
 
@@ -115,17 +170,24 @@ impl MolarWeight<SIUnit> for PetsFunctional {
     }
 }
 
-impl FMTProperties for PetsParameters {
+/// Parameters and options that determine the hard-sphere diameters
+/// of the fundamental measure theory functional.
+pub struct PetsFMTProperties {
+    parameters: Rc<PetsParameters>,
+    options: PetsOptions,
+}
+
+impl FMTProperties for PetsFMTProperties {
     fn component_index(&self) -> Array1<usize> {
-        Array::from_shape_fn(self.sigma.len(), |i| i)
+        Array::from_shape_fn(self.parameters.sigma.len(), |i| i)
     }
 
     fn chain_length(&self) -> Array1<f64> {
-        Array1::<f64>::ones(self.sigma.len())
+        self.parameters.m.clone()
     }
 
     fn hs_diameter<D: DualNum<f64>>(&self, temperature: D) -> Array1<D> {
-        self.hs_diameter(temperature)
+        self.parameters.hs_diameter(temperature, &self.options)
     }
 }
 
@@ -141,19 +203,16 @@ impl FluidParameters for PetsFunctional {
 
 impl PairPotential for PetsFunctional {
     fn pair_potential(&self, r: &Array1<f64>) -> Array2<f64> {
-        let sigma = &self.parameters.sigma;
-        let rc = 2.5 * sigma;
-        let u_rc = 4.0
-            * &self.parameters.epsilon_k
-            * ((sigma / &rc).mapv(|l| l.powi(12)) - (sigma / &rc).mapv(|l| l.powi(6)));
-
-        Array::from_shape_fn((self.parameters.sigma.len(), r.len()), |(i, j)| {
-            if r[j] > rc[i] {
+        let p = &self.parameters;
+        let rc = PETS_CUTOFF;
+        Array::from_shape_fn((p.sigma.len(), r.len()), |(i, j)| {
+            let x = r[j] / p.sigma[i];
+            if x > rc {
                 0.0
+            } else if self.options.tail_correction {
+                p.epsilon_k[i] * u_mie(x, p.lambda_r[i])
             } else {
-                4.0 * self.parameters.epsilon_k[i]
-                    * ((sigma[i] / r[j]).powi(12) - (sigma[i] / r[j]).powi(6))
-                    - u_rc[i]
+                p.epsilon_k[i] * (u_mie(x, p.lambda_r[i]) - u_mie(rc, p.lambda_r[i]))
             }
         })
     }
@@ -0,0 +1,229 @@
+use super::dispersion::{att_weight_functions, PSI_DFT, PSI_PDGT};
+use crate::eos::polar::{
+    dipole_helmholtz_energy_density, dipole_quadrupole_helmholtz_energy_density,
+    quadrupole_helmholtz_energy_density,
+};
+use crate::eos::PetsOptions;
+use crate::parameters::PetsParameters;
+use feos_core::EosError;
+use feos_dft::{FunctionalContributionDual, WeightFunctionInfo};
+use ndarray::*;
+use num_dual::DualNum;
+use std::fmt;
+use std::rc::Rc;
+
+/// Dipole-dipole functional evaluated with the weighted densities
+/// of the attractive functional.
+#[derive(Clone)]
+pub struct DipoleFunctional {
+    parameters: Rc<PetsParameters>,
+    options: PetsOptions,
+}
+
+impl DipoleFunctional {
+    pub fn new(parameters: Rc<PetsParameters>, options: PetsOptions) -> Self {
+        Self {
+            parameters,
+            options,
+        }
+    }
+}
+
+impl<N: DualNum<f64> + ScalarOperand> FunctionalContributionDual<N> for DipoleFunctional {
+    fn weight_functions(&self, temperature: N) -> WeightFunctionInfo<N> {
+        att_weight_functions(&self.parameters, &self.options, PSI_DFT, temperature)
+    }
+
+    fn weight_functions_pdgt(&self, temperature: N) -> WeightFunctionInfo<N> {
+        att_weight_functions(&self.parameters, &self.options, PSI_PDGT, temperature)
+    }
+
+    fn calculate_helmholtz_energy_density(
+        &self,
+        temperature: N,
+        density: ArrayView2<N>,
+    ) -> Result<Array1<N>, EosError> {
+        let (p, options) = (&self.parameters, &self.options);
+        let d = p.hs_diameter(temperature, options);
+        Ok(density
+            .axis_iter(Axis(1))
+            .map(|rho| dipole_helmholtz_energy_density(p, options, temperature, &d, rho))
+            .collect())
+    }
+}
+
+impl fmt::Display for DipoleFunctional {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Dipole functional")
+    }
+}
+
+/// Quadrupole-quadrupole functional evaluated with the weighted densities
+/// of the attractive functional.
+#[derive(Clone)]
+pub struct QuadrupoleFunctional {
+    parameters: Rc<PetsParameters>,
+    options: PetsOptions,
+}
+
+impl QuadrupoleFunctional {
+    pub fn new(parameters: Rc<PetsParameters>, options: PetsOptions) -> Self {
+        Self {
+            parameters,
+            options,
+        }
+    }
+}
+
+impl<N: DualNum<f64> + ScalarOperand> FunctionalContributionDual<N> for QuadrupoleFunctional {
+    fn weight_functions(&self, temperature: N) -> WeightFunctionInfo<N> {
+        att_weight_functions(&self.parameters, &self.options, PSI_DFT, temperature)
+    }
+
+    fn weight_functions_pdgt(&self, temperature: N) -> WeightFunctionInfo<N> {
+        att_weight_functions(&self.parameters, &self.options, PSI_PDGT, temperature)
+    }
+
+    fn calculate_helmholtz_energy_density(
+        &self,
+        temperature: N,
+        density: ArrayView2<N>,
+    ) -> Result<Array1<N>, EosError> {
+        let (p, options) = (&self.parameters, &self.options);
+        let d = p.hs_diameter(temperature, options);
+        Ok(density
+            .axis_iter(Axis(1))
+            .map(|rho| quadrupole_helmholtz_energy_density(p, options, temperature, &d, rho))
+            .collect())
+    }
+}
+
+impl fmt::Display for QuadrupoleFunctional {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Quadrupole functional")
+    }
+}
+
+/// Dipole-quadrupole functional evaluated with the weighted densities
+/// of the attractive functional.
+#[derive(Clone)]
+pub struct DipoleQuadrupoleFunctional {
+    parameters: Rc<PetsParameters>,
+    options: PetsOptions,
+}
+
+impl DipoleQuadrupoleFunctional {
+    pub fn new(parameters: Rc<PetsParameters>, options: PetsOptions) -> Self {
+        Self {
+            parameters,
+            options,
+        }
+    }
+}
+
+impl<N: DualNum<f64> + ScalarOperand> FunctionalContributionDual<N> for DipoleQuadrupoleFunctional {
+    fn weight_functions(&self, temperature: N) -> WeightFunctionInfo<N> {
+        att_weight_functions(&self.parameters, &self.options, PSI_DFT, temperature)
+    }
+
+    fn weight_functions_pdgt(&self, temperature: N) -> WeightFunctionInfo<N> {
+        att_weight_functions(&self.parameters, &self.options, PSI_PDGT, temperature)
+    }
+
+    fn calculate_helmholtz_energy_density(
+        &self,
+        temperature: N,
+        density: ArrayView2<N>,
+    ) -> Result<Array1<N>, EosError> {
+        let (p, options) = (&self.parameters, &self.options);
+        let d = p.hs_diameter(temperature, options);
+        Ok(density
+            .axis_iter(Axis(1))
+            .map(|rho| dipole_quadrupole_helmholtz_energy_density(p, options, temperature, &d, rho))
+            .collect())
+    }
+}
+
+impl fmt::Display for DipoleQuadrupoleFunctional {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Dipole-quadrupole functional")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dft::PetsFunctional;
+    use crate::eos::Pets;
+    use crate::parameters::utils::{argon_krypton_parameters, argon_parameters, map_model_records};
+    use approx::assert_relative_eq;
+    use feos_core::{Contributions, PhaseEquilibrium, SolverOptions, State};
+    use feos_dft::interface::PlanarInterface;
+    use ndarray::arr1;
+    use quantity::si::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn bulk_consistency() {
+        let t = 200.0 * KELVIN;
+        let v = 1e-4 * METER.powi(3);
+        for (parameters, n) in [
+            (
+                map_model_records(&argon_parameters(), |_, r| r.with_dipole_moment(1.5)),
+                arr1(&[1.0]) * MOL,
+            ),
+            (
+                map_model_records(&argon_krypton_parameters(), |i, r| {
+                    r.with_dipole_moment([1.5, 2.0][i])
+                }),
+                arr1(&[0.3, 0.7]) * MOL,
+            ),
+            (
+                map_model_records(&argon_krypton_parameters(), |i, r| {
+                    r.with_quadrupole_moment([3.0, 4.0][i])
+                }),
+                arr1(&[0.3, 0.7]) * MOL,
+            ),
+            (
+                map_model_records(&argon_krypton_parameters(), |i, r| {
+                    r.with_dipole_moment([1.5, 0.0][i])
+                        .with_quadrupole_moment([0.0, 4.0][i])
+                }),
+                arr1(&[0.3, 0.7]) * MOL,
+            ),
+        ] {
+            let eos = Rc::new(Pets::new(parameters.clone()));
+            let func = Rc::new(PetsFunctional::new(parameters));
+            let s_eos = State::new_nvt(&eos, t, v, &n).unwrap();
+            let s_func = State::new_nvt(&func, t, v, &n).unwrap();
+            assert_relative_eq!(
+                s_eos.pressure(Contributions::Total),
+                s_func.pressure(Contributions::Total),
+                max_relative = 1e-10
+            );
+            assert_relative_eq!(
+                s_eos.chemical_potential(Contributions::ResidualNvt),
+                s_func.chemical_potential(Contributions::ResidualNvt),
+                max_relative = 1e-10
+            );
+        }
+    }
+
+    #[test]
+    fn surface_tension() {
+        let surface_tension = |mu: f64| {
+            let parameters =
+                map_model_records(&argon_parameters(), |_, r| r.with_dipole_moment(mu));
+            let func = Rc::new(PetsFunctional::new(parameters));
+            let vle = PhaseEquilibrium::pure(&func, 100.0 * KELVIN, None, SolverOptions::default())
+                .unwrap();
+            PlanarInterface::from_tanh(&vle, 512, 100.0 * ANGSTROM, 500.0 * KELVIN)
+                .unwrap()
+                .solve(None)
+                .unwrap()
+                .surface_tension
+                .unwrap()
+        };
+        // dipole-dipole interactions increase the cohesion of the liquid
+        assert!(surface_tension(1.0) > surface_tension(0.0));
+    }
+}
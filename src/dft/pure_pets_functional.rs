@@ -1,4 +1,5 @@
 use crate::eos::dispersion::{A, B};
+use crate::eos::PetsOptions;
 use crate::parameters::PetsParameters;
 use feos_core::{EosError, EosResult};
 use feos_dft::fundamental_measure_theory::FMTVersion;
@@ -17,13 +18,15 @@ const N3_CUTOFF: f64 = 1e-5;
 #[derive(Clone)]
 pub struct PureFMTFunctional {
     parameters: Rc<PetsParameters>,
+    options: PetsOptions,
     version: FMTVersion,
 }
 
 impl PureFMTFunctional {
-    pub fn new(parameters: Rc<PetsParameters>, version: FMTVersion) -> Self {
+    pub fn new(parameters: Rc<PetsParameters>, options: PetsOptions, version: FMTVersion) -> Self {
         Self {
             parameters,
+            options,
             version,
         }
     }
@@ -31,7 +34,7 @@ impl PureFMTFunctional {
 
 impl<N: DualNum<f64> + ScalarOperand> FunctionalContributionDual<N> for PureFMTFunctional {
     fn weight_functions(&self, temperature: N) -> WeightFunctionInfo<N> {
-        let r = self.parameters.hs_diameter(temperature) * 0.5;
+        let r = self.parameters.hs_diameter(temperature, &self.options) * 0.5;
         WeightFunctionInfo::new(arr1(&[0]), false).extend(
             vec![
                 WeightFunctionShape::Delta,
@@ -60,7 +63,7 @@ impl<N: DualNum<f64> + ScalarOperand> FunctionalContributionDual<N> for PureFMTF
         let n2v = weighted_densities.slice_axis(Axis(0), Slice::new(2, None, 1));
 
         // Temperature dependent segment radius
-        let r = self.parameters.hs_diameter(temperature)[0] * 0.5;
+        let r = self.parameters.hs_diameter(temperature, &self.options)[0] * 0.5;
 
         // Auxiliary variables
         if n3.iter().any(|n3| n3.re() > 1.0) {
@@ -117,19 +120,21 @@ impl fmt::Display for PureFMTFunctional {
 #[derive(Clone)]
 pub struct PureAttFunctional {
     parameters: Rc<PetsParameters>,
+    options: PetsOptions,
 }
 
 impl PureAttFunctional {
-    pub fn new(parameters: Rc<PetsParameters>) -> Self {
+    pub fn new(parameters: Rc<PetsParameters>, options: PetsOptions) -> Self {
         Self {
-            parameters: parameters,
+            parameters,
+            options,
         }
     }
 }
 
 impl<N: DualNum<f64> + ScalarOperand> FunctionalContributionDual<N> for PureAttFunctional {
     fn weight_functions(&self, temperature: N) -> WeightFunctionInfo<N> {
-        let d = self.parameters.hs_diameter(temperature);
+        let d = self.parameters.hs_diameter(temperature, &self.options);
         const PSI: f64 = 1.21; // Homosegmented DFT (Heier2018)
         WeightFunctionInfo::new(arr1(&[0]), false).add(
             WeightFunction::new_scaled(d * PSI, WeightFunctionShape::Theta),
@@ -138,7 +143,7 @@ impl<N: DualNum<f64> + ScalarOperand> FunctionalContributionDual<N> for PureAttF
     }
 
     fn weight_functions_pdgt(&self, temperature: N) -> WeightFunctionInfo<N> {
-        let d = self.parameters.hs_diameter(temperature);
+        let d = self.parameters.hs_diameter(temperature, &self.options);
         const PSI: f64 = 1.21; // pDGT (not yet determined)
         WeightFunctionInfo::new(arr1(&[0]), false).add(
             WeightFunction::new_scaled(d * PSI, WeightFunctionShape::Theta),
@@ -155,11 +160,14 @@ impl<N: DualNum<f64> + ScalarOperand> FunctionalContributionDual<N> for PureAttF
         let rho = weighted_densities.index_axis(Axis(0), 0);
 
         // temperature dependent segment radius
-        let d = p.hs_diameter(temperature)[0];
+        let d = p.hs_diameter(temperature, &self.options)[0];
 
         let eta = rho.mapv(|rho| rho * FRAC_PI_6 * d.powi(3));
-        let e = temperature.recip() * p.epsilon_k[0];
-        let s3 = p.sigma[0].powi(3);
+        let (sigma, epsilon_k) = p.effective_parameters(temperature, self.options.feynman_hibbs);
+        let e = temperature.recip() * epsilon_k[0];
+        let s3 = sigma[0].powi(3);
+        let (s1, s2) = p.dispersion_scaling();
+        let (s1, s2) = (s1[(0, 0)], s2[(0, 0)]);
 
         // I1, I2 and C1
         let mut i1: Array1<N> = Array::zeros(eta.raw_dim());
@@ -170,8 +178,8 @@ impl<N: DualNum<f64> + ScalarOperand> FunctionalContributionDual<N> for PureAttF
         }
         let c1 =
             eta.mapv(|eta| ((eta * 8.0 - eta.powi(2) * 2.0) / (eta - 1.0).powi(4) + 1.0).recip());
-        let phi =
-            rho.mapv(|rho| -(rho).powi(2) * e * s3 * PI) * (i1 * 2.0 + c1 * i2.mapv(|i2| i2 * e));
+        let phi = rho.mapv(|rho| -(rho).powi(2) * e * s3 * PI)
+            * (i1 * (2.0 * s1) + c1 * i2.mapv(|i2| i2 * e * s2));
 
         Ok(phi)
     }
@@ -0,0 +1,327 @@
+use super::hard_sphere::{hs_contact_value, zeta};
+use super::PetsOptions;
+use crate::parameters::PetsParameters;
+use feos_core::{EosError, EosResult, HelmholtzEnergyDual, StateHD};
+use ndarray::*;
+use num_dual::linalg::{norm, LU};
+use num_dual::DualNum;
+use std::fmt;
+use std::rc::Rc;
+
+const MAX_ITER: usize = 50;
+const TOL: f64 = 1e-10;
+
+/// Association strength between sites A of component `i` and B of component `j`
+/// (and vice versa) in units of Angstrom³.
+///
+/// The association volume is scaled with the cross segment diameter `sigma_ij`.
+#[allow(clippy::too_many_arguments)]
+fn association_strength<D: DualNum<f64>>(
+    p: &PetsParameters,
+    temperature: D,
+    sigma_ij: &Array2<D>,
+    diameter: &Array1<D>,
+    zeta2: D,
+    zeta3: D,
+    i: usize,
+    j: usize,
+) -> D {
+    let d_ij = diameter[i] * diameter[j] / (diameter[i] + diameter[j]);
+    let k_ij = sigma_ij[(i, j)].powi(3) * (p.kappa_ab[i] * p.kappa_ab[j]).sqrt();
+    let epsilon_k_ij = 0.5 * (p.epsilon_k_ab[i] + p.epsilon_k_ab[j]);
+    hs_contact_value(zeta2, zeta3, d_ij) * k_ij * (temperature.recip() * epsilon_k_ij).exp_m1()
+}
+
+/// Reduced Helmholtz energy density of the association.
+///
+/// The fractions of non-bonded sites are calculated with a Newton
+/// iteration. If the iteration does not converge, `EosError::NotConverged`
+/// is returned.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn association_helmholtz_energy_density<D: DualNum<f64>>(
+    p: &PetsParameters,
+    options: &PetsOptions,
+    temperature: D,
+    diameter: &Array1<D>,
+    density: ArrayView1<D>,
+    zeta2: D,
+    zeta3: D,
+) -> EosResult<D> {
+    let assoc: Vec<_> = (0..p.kappa_ab.len())
+        .filter(|&i| p.kappa_ab[i] != 0.0)
+        .collect();
+    let a = assoc.len();
+    let (sigma_ij, _) = p.effective_cross_parameters(temperature, options.feynman_hibbs);
+    let delta = Array2::from_shape_fn((a, a), |(i, j)| {
+        association_strength(
+            p,
+            temperature,
+            &sigma_ij,
+            diameter,
+            zeta2,
+            zeta3,
+            assoc[i],
+            assoc[j],
+        )
+    });
+    // densities of the sites of type A and B
+    let rho_a: Array1<D> = assoc.iter().map(|&i| density[i] * p.na[i]).collect();
+    let rho_b: Array1<D> = assoc.iter().map(|&i| density[i] * p.nb[i]).collect();
+
+    // fractions of non-bonded sites [X_A, X_B]
+    let mut x: Array1<D> = Array::from_elem(2 * a, D::from(0.2));
+    let mut converged = None;
+    for k in 0..MAX_ITER {
+        let (x_a, x_b) = x.view().split_at(Axis(0), a);
+        let sum_a = delta.dot(&(&rho_b * &x_b));
+        let sum_b = delta.t().dot(&(&rho_a * &x_a));
+        let mut g = Array::zeros(2 * a);
+        let mut jacobian = Array::zeros((2 * a, 2 * a));
+        for i in 0..a {
+            g[i] = x_a[i] * (sum_a[i] + 1.0) - 1.0;
+            g[a + i] = x_b[i] * (sum_b[i] + 1.0) - 1.0;
+            jacobian[(i, i)] = sum_a[i] + 1.0;
+            jacobian[(a + i, a + i)] = sum_b[i] + 1.0;
+            for j in 0..a {
+                jacobian[(i, a + j)] = x_a[i] * rho_b[j] * delta[(i, j)];
+                jacobian[(a + i, j)] = x_b[i] * rho_a[j] * delta[(j, i)];
+            }
+        }
+        let dx = LU::new(jacobian)
+            .map_err(|_| EosError::NotConverged("association".into()))?
+            .solve(&g);
+        x = Zip::from(&x).and(&dx).map_collect(|&x, &dx| {
+            let x_new = x - dx;
+            // damping to keep the fractions positive
+            if x_new.re() > 0.0 {
+                x_new
+            } else {
+                x * 0.2
+            }
+        });
+        // additional steps after the convergence of the real part
+        // provide the derivatives of the fractions
+        match converged {
+            Some(c) if k >= c + 3 => break,
+            None if norm(&dx.mapv(|dx| dx.re())) < TOL => converged = Some(k),
+            _ => (),
+        }
+    }
+    if converged.is_none() {
+        return Err(EosError::NotConverged("association".into()));
+    }
+
+    let (x_a, x_b) = x.view().split_at(Axis(0), a);
+    Ok(Zip::from(&rho_a)
+        .and(&x_a)
+        .fold(D::zero(), |acc, &rho, &x| {
+            acc + rho * (x.ln() - x * 0.5 + 0.5)
+        })
+        + Zip::from(&rho_b)
+            .and(&x_b)
+            .fold(D::zero(), |acc, &rho, &x| {
+                acc + rho * (x.ln() - x * 0.5 + 0.5)
+            }))
+}
+
+/// Wertheim TPT1 association contribution with sites of type A and B
+/// that only bond with sites of the other type.
+///
+/// The association strength uses the contact value of the PeTS hard-sphere fluid.
+/// Since the Helmholtz energy of an equation of state cannot return an error,
+/// states for which the fractions of non-bonded sites do not converge result
+/// in a Helmholtz energy of NaN.
+#[derive(Debug, Clone)]
+pub struct Association {
+    pub parameters: Rc<PetsParameters>,
+    pub options: PetsOptions,
+}
+
+impl<D: DualNum<f64>> HelmholtzEnergyDual<D> for Association {
+    fn helmholtz_energy(&self, state: &StateHD<D>) -> D {
+        let p = &self.parameters;
+        let d = p.hs_diameter(state.temperature, &self.options);
+        let [_, _, zeta2, zeta3] = zeta(&p.m, &state.partial_density, &d);
+        association_helmholtz_energy_density(
+            p,
+            &self.options,
+            state.temperature,
+            &d,
+            state.partial_density.view(),
+            zeta2,
+            zeta3,
+        )
+        .map_or(D::from(f64::NAN), |a| a * state.volume)
+    }
+}
+
+impl fmt::Display for Association {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Association")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parameters::utils::{argon_krypton_parameters, argon_parameters, map_model_records};
+    use crate::parameters::{CombiningRule, PetsBinaryRecord};
+    use approx::assert_relative_eq;
+    use feos_core::parameter::Parameter;
+    use num_dual::{Dual64, HyperDual64};
+
+    #[test]
+    fn two_site_analytic() {
+        let parameters = map_model_records(&argon_parameters(), |_, r| {
+            r.with_association(0.03, 2000.0, 1.0, 1.0)
+        });
+        let t = 300.0;
+        let v = 1e4;
+        let n = 100.0;
+        let a = Association {
+            parameters: parameters.clone(),
+            options: PetsOptions::default(),
+        }
+        .helmholtz_energy(&StateHD::new(t, v, arr1(&[n])));
+
+        // symmetric 2B site: X = (-1 + sqrt(1 + 4 rho Delta)) / (2 rho Delta)
+        let rho = n / v;
+        let d = parameters.hs_diameter(t, &PetsOptions::default());
+        let [_, _, zeta2, zeta3] = zeta(&parameters.m, &arr1(&[rho]), &d);
+        let sigma_ij = parameters.sigma_ij.clone();
+        let delta = association_strength(&parameters, t, &sigma_ij, &d, zeta2, zeta3, 0, 0);
+        let x = (-1.0 + (1.0 + 4.0 * rho * delta).sqrt()) / (2.0 * rho * delta);
+        assert!(x < 0.5);
+        assert_relative_eq!(a, 2.0 * n * (x.ln() - 0.5 * x + 0.5), max_relative = 1e-12);
+    }
+
+    #[test]
+    fn low_density_limit_cross_association() {
+        // a / V = -sum_ij rho_i n_A,i rho_j n_B,j Delta_ij with the cross diameter sigma_ij
+        let pure_records = map_model_records(&argon_krypton_parameters(), |i, r| {
+            r.with_association([0.03, 0.01][i], [2000.0, 1500.0][i], 1.0, 1.0)
+        })
+        .pure_records
+        .clone();
+        let parameters = Rc::new(
+            PetsParameters::new_binary(pure_records, Some(PetsBinaryRecord::new(0.0, 0.0, 0.05)))
+                .with_combining_rule(CombiningRule::WaldmanHagler),
+        );
+        let t = 300.0;
+        let n = [0.3, 0.7];
+        let v = 1e12;
+        let a = Association {
+            parameters: parameters.clone(),
+            options: PetsOptions::default(),
+        }
+        .helmholtz_energy(&StateHD::new(t, v, arr1(&n)));
+        let mut expected = 0.0;
+        for i in 0..2 {
+            for j in 0..2 {
+                let p = &parameters;
+                let epsilon_k_ab = 0.5 * (p.epsilon_k_ab[i] + p.epsilon_k_ab[j]);
+                let delta = p.sigma_ij[[i, j]].powi(3)
+                    * (p.kappa_ab[i] * p.kappa_ab[j]).sqrt()
+                    * (epsilon_k_ab / t).exp_m1();
+                expected -= n[i] * n[j] * delta;
+            }
+        }
+        assert_relative_eq!(a * v, expected, max_relative = 1e-6);
+    }
+
+    #[test]
+    fn not_converged() {
+        let parameters = map_model_records(&argon_parameters(), |_, r| {
+            r.with_association(0.03, 2000.0, 1.0, 1.0)
+        });
+        let t = 300.0;
+        let density = arr1(&[f64::NAN]);
+        let options = PetsOptions::default();
+        let d = parameters.hs_diameter(t, &options);
+        let [_, _, zeta2, zeta3] = zeta(&parameters.m, &density, &d);
+        let a = association_helmholtz_energy_density(
+            &parameters,
+            &options,
+            t,
+            &d,
+            density.view(),
+            zeta2,
+            zeta3,
+        );
+        assert!(matches!(a, Err(EosError::NotConverged(_))));
+    }
+
+    #[test]
+    fn inert_component() {
+        let t = 300.0;
+        let v = 1e4;
+        let n = 100.0;
+        let pure = Association {
+            parameters: map_model_records(&argon_parameters(), |_, r| {
+                r.with_association(0.03, 2000.0, 2.0, 1.0)
+            }),
+            options: PetsOptions::default(),
+        }
+        .helmholtz_energy(&StateHD::new(t, v, arr1(&[n])));
+        let mix = Association {
+            parameters: map_model_records(&argon_krypton_parameters(), |i, r| {
+                if i == 0 {
+                    r.with_association(0.03, 2000.0, 2.0, 1.0)
+                } else {
+                    r
+                }
+            }),
+            options: PetsOptions::default(),
+        };
+        assert_relative_eq!(
+            pure,
+            mix.helmholtz_energy(&StateHD::new(t, v, arr1(&[n, 0.0]))),
+            max_relative = 1e-12
+        );
+        assert_eq!(
+            mix.helmholtz_energy(&StateHD::new(t, v, arr1(&[0.0, n]))),
+            0.0
+        );
+    }
+
+    #[test]
+    fn derivatives() {
+        let parameters = map_model_records(&argon_krypton_parameters(), |i, r| {
+            r.with_association([0.03, 0.01][i], [2000.0, 1500.0][i], 1.0, [1.0, 2.0][i])
+        });
+        let assoc = Association {
+            parameters,
+            options: PetsOptions::default(),
+        };
+        let t = 300.0;
+        let v = 1e4;
+        let n = arr1(&[60.0, 40.0]);
+        let a = |v: f64| assoc.helmholtz_energy(&StateHD::new(t, v, n.clone()));
+        let h = 1.0;
+
+        // first derivative
+        let a_dual = assoc.helmholtz_energy(&StateHD::new(
+            Dual64::from(t),
+            Dual64::from(v).derive(),
+            n.mapv(Dual64::from),
+        ));
+        assert_relative_eq!(
+            a_dual.eps[0],
+            (a(v + h) - a(v - h)) / (2.0 * h),
+            max_relative = 1e-6
+        );
+
+        // second derivative
+        let a_hyperdual = assoc.helmholtz_energy(&StateHD::new(
+            HyperDual64::from(t),
+            HyperDual64::from(v).derive1().derive2(),
+            n.mapv(HyperDual64::from),
+        ));
+        assert_relative_eq!(a_hyperdual.re, a(v), max_relative = 1e-14);
+        assert_relative_eq!(
+            a_hyperdual.eps1eps2[(0, 0)],
+            (a(v + h) - 2.0 * a(v) + a(v - h)) / (h * h),
+            max_relative = 1e-4
+        );
+    }
+}
@@ -0,0 +1,176 @@
+use super::mie::{mie_prefactor, u_mie};
+use super::PetsOptions;
+use crate::parameters::PetsParameters;
+use feos_core::{HelmholtzEnergyDual, StateHD};
+use num_dual::DualNum;
+use std::f64::consts::PI;
+use std::fmt;
+use std::rc::Rc;
+
+/// Cutoff radius (in units of sigma) of the potential the PeTS equation of state is adjusted to.
+pub const PETS_CUTOFF: f64 = 2.5;
+
+/// Cutoff radius at which the potential in `options` is shifted to 0,
+/// infinite for the potential with the tail correction.
+pub(crate) fn shifted_cutoff(options: &PetsOptions) -> f64 {
+    if options.tail_correction {
+        f64::INFINITY
+    } else {
+        PETS_CUTOFF
+    }
+}
+
+/// Antiderivative of `x² u(x)` of the Mie n-6 potential.
+fn antiderivative(x: f64, n: f64) -> f64 {
+    mie_prefactor(n) * (x.powi(-3) / 3.0 + x.powf(3.0 - n) / (3.0 - n))
+}
+
+/// Long-range correction, i.e., the mean-field integral over the
+/// Mie n-6 potential beyond `cutoff`.
+pub(crate) fn long_range_correction(cutoff: f64, n: f64) -> f64 {
+    -antiderivative(cutoff, n)
+}
+
+/// Difference of the mean-field integrals of the full Mie n-6 potential
+/// and the potential truncated and shifted at [PETS_CUTOFF].
+///
+/// The difference consists of the long-range correction beyond the
+/// cutoff and the shift of the potential inside the cutoff. The pair
+/// correlation function is approximated as 0 below the reduced
+/// hard-sphere diameter `d` and as 1 above.
+pub(crate) fn tail_correction<D: DualNum<f64>>(d: D, n: f64) -> D {
+    let cutoff = PETS_CUTOFF;
+    (-d.powi(3) + cutoff.powi(3)) * u_mie(cutoff, n) / 3.0 + long_range_correction(cutoff, n)
+}
+
+/// First-order mean-field long-range correction of PeTS.
+///
+/// With `tail_correction`, the potential is truncated at 2.5 sigma but not
+/// shifted and the long-range correction beyond the cutoff is added, which
+/// is equivalent to the full potential in mean-field theory. The difference
+/// to the potential truncated and shifted at 2.5 sigma is treated as a
+/// perturbation to PeTS. For Mie potentials, the correction is evaluated
+/// with the repulsive exponent of each pair of components.
+#[derive(Debug, Clone)]
+pub struct CutoffCorrection {
+    pub parameters: Rc<PetsParameters>,
+    pub options: PetsOptions,
+}
+
+impl<D: DualNum<f64>> HelmholtzEnergyDual<D> for CutoffCorrection {
+    fn helmholtz_energy(&self, state: &StateHD<D>) -> D {
+        let p = &self.parameters;
+        let n = p.sigma.len();
+        let rho = &state.partial_density;
+        let d = p.hs_diameter(state.temperature, &self.options);
+        let (sigma_ij, epsilon_k_ij) =
+            p.effective_cross_parameters(state.temperature, self.options.feynman_hibbs);
+
+        let mut a = D::zero();
+        for i in 0..n {
+            for j in 0..n {
+                let d_ij = (d[i] + d[j]) * 0.5 / sigma_ij[(i, j)];
+                a += rho[i] * rho[j] * epsilon_k_ij[(i, j)] / state.temperature
+                    * sigma_ij[(i, j)].powi(3)
+                    * (p.m[i] * p.m[j])
+                    * tail_correction(d_ij, p.lambda_r_ij(i, j));
+            }
+        }
+        a * 2.0 * PI * state.volume
+    }
+}
+
+impl fmt::Display for CutoffCorrection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Long-range correction (rc = {} sigma)", PETS_CUTOFF)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parameters::utils::{argon_parameters, map_model_records};
+    use approx::assert_relative_eq;
+    use ndarray::arr1;
+
+    /// Trapezoidal rule of the difference of the full Mie n-6 potential and
+    /// the potential truncated and shifted at [PETS_CUTOFF] from `d`.
+    fn tail_correction_trapezoidal(d: f64, n: f64) -> f64 {
+        let integral = |u_c: f64| {
+            let points = 200000;
+            let dx = (PETS_CUTOFF - d) / points as f64;
+            (0..=points)
+                .map(|i| {
+                    let x = d + i as f64 * dx;
+                    let w = if i == 0 || i == points { 0.5 } else { 1.0 };
+                    w * (u_mie(x, n) - u_c) * x * x * dx
+                })
+                .sum::<f64>()
+        };
+        integral(0.0) + long_range_correction(PETS_CUTOFF, n) - integral(u_mie(PETS_CUTOFF, n))
+    }
+
+    #[test]
+    fn tail_correction_numerical() {
+        let d = 0.95;
+        for n in [12.0, 10.0] {
+            assert_relative_eq!(
+                tail_correction(d, n),
+                tail_correction_trapezoidal(d, n),
+                max_relative = 1e-8
+            );
+        }
+    }
+
+    #[test]
+    fn mie_exponent() {
+        // the correction uses the exact integral of the Mie potential, not a
+        // rescaled Lennard-Jones correction
+        let (sigma, epsilon_k) = (3.405, 119.8);
+        let (t, v, rho) = (150.0, 1e5, 0.01);
+        let options = PetsOptions {
+            tail_correction: true,
+            ..Default::default()
+        };
+        for n in [10.0, 12.0, 16.0] {
+            let parameters =
+                map_model_records(&argon_parameters(), |_, r| r.with_repulsive_exponent(n));
+            let d = parameters.hs_diameter(t, &options)[0] / sigma;
+            let correction = CutoffCorrection {
+                parameters,
+                options,
+            };
+            let a = correction.helmholtz_energy(&StateHD::new(t, v, arr1(&[rho * v])));
+            assert_relative_eq!(
+                a,
+                2.0 * PI * rho * rho * v * epsilon_k / t
+                    * sigma.powi(3)
+                    * tail_correction_trapezoidal(d, n),
+                max_relative = 1e-8
+            );
+        }
+    }
+
+    #[test]
+    fn long_range_correction_numerical() {
+        // substitution y = 1 / x and trapezoidal rule from 0 to 1 / cutoff
+        for n in [12.0, 10.0] {
+            for cutoff in [2.5, 4.0] {
+                let points = 200000;
+                let dy = 1.0 / cutoff / points as f64;
+                let integral = (1..=points)
+                    .map(|i| {
+                        let y = i as f64 * dy;
+                        let w = if i == points { 0.5 } else { 1.0 };
+                        w * u_mie(1.0 / y, n) * y.powi(-4) * dy
+                    })
+                    .sum::<f64>();
+                assert_relative_eq!(
+                    long_range_correction(cutoff, n),
+                    integral,
+                    max_relative = 1e-8
+                );
+            }
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use super::PetsOptions;
 use crate::parameters::PetsParameters;
 use feos_core::{HelmholtzEnergyDual, StateHD};
 use num_dual::DualNum;
@@ -24,9 +25,22 @@ pub const B: [f64; 7] = [
     -353.2743581,
 ];
 
+/// Compressibility term of the dispersion contribution for chains
+/// with the mean segment number `m`.
+///
+/// The coefficients `A` and `B` of the PeTS equation of state are not
+/// chain-length dependent, only `C1` includes the hard-chain compressibility.
+pub(crate) fn c1<D: DualNum<f64>>(eta: D, m: D) -> D {
+    let hs = (eta * 8.0 - eta.powi(2) * 2.0) / (eta - 1.0).powi(4);
+    let chain = (eta * 20.0 - eta.powi(2) * 27.0 + eta.powi(3) * 12.0 - eta.powi(4) * 2.0)
+        / ((eta - 1.0) * (eta - 2.0)).powi(2);
+    (m * hs - (m - 1.0) * chain + 1.0).recip()
+}
+
 #[derive(Debug, Clone)]
 pub struct Dispersion {
     pub parameters: Rc<PetsParameters>,
+    pub options: PetsOptions,
 }
 
 impl<D: DualNum<f64>> HelmholtzEnergyDual<D> for Dispersion {
@@ -37,20 +51,32 @@ impl<D: DualNum<f64>> HelmholtzEnergyDual<D> for Dispersion {
         let rho = &state.partial_density;
 
         // temperature dependent segment radius
-        let r = p.hs_diameter(state.temperature) * 0.5;
+        let r = p.hs_diameter(state.temperature, &self.options) * 0.5;
 
         // packing fraction
-        let eta = (rho * &r * &r * &r).sum() * 4.0 * FRAC_PI_3;
+        let eta = (rho * &r * &r * &r * &p.m.mapv(D::from)).sum() * 4.0 * FRAC_PI_3;
+
+        // mean segment number
+        let m = if p.has_chains() {
+            (&state.molefracs * &p.m.mapv(D::from)).sum()
+        } else {
+            D::one()
+        };
+
+        // temperature dependent cross parameters
+        let (sigma_ij, epsilon_k_ij) =
+            p.effective_cross_parameters(state.temperature, self.options.feynman_hibbs);
+        let (s1, s2) = p.dispersion_scaling();
 
         // mixture densities, crosswise interactions of all segments on all chains
         let mut rho1mix = D::zero();
         let mut rho2mix = D::zero();
         for i in 0..n {
             for j in 0..n {
-                let eps_ij = state.temperature.recip() * p.epsilon_k_ij[(i, j)];
-                let sigma_ij = p.sigma_ij[[i, j]].powi(3);
-                rho1mix += rho[i] * rho[j] * eps_ij * sigma_ij;
-                rho2mix += rho[i] * rho[j] * eps_ij * eps_ij * sigma_ij;
+                let eps_ij = epsilon_k_ij[(i, j)] / state.temperature;
+                let sigma_ij_3 = sigma_ij[[i, j]].powi(3) * (p.m[i] * p.m[j]);
+                rho1mix += rho[i] * rho[j] * eps_ij * sigma_ij_3 * s1[(i, j)];
+                rho2mix += rho[i] * rho[j] * eps_ij * eps_ij * sigma_ij_3 * s2[(i, j)];
             }
         }
 
@@ -63,10 +89,10 @@ impl<D: DualNum<f64>> HelmholtzEnergyDual<D> for Dispersion {
             i2 += eta_i * B[i];
             eta_i *= eta;
         }
-        let c1 = ((eta * 8.0 - eta.powi(2) * 2.0) / (eta - 1.0).powi(4) + 1.0).recip();
+        let c1 = c1(eta, m);
 
         // Helmholtz energy
-        (-rho1mix * i1 * 2.0 - rho2mix * c1 * i2) * PI * state.volume
+        (-rho1mix * i1 * 2.0 - rho2mix * m * c1 * i2) * PI * state.volume
     }
 }
 
@@ -82,19 +108,25 @@ mod tests {
     use crate::parameters::utils::{
         argon_krypton_parameters, argon_parameters, krypton_parameters,
     };
+    use crate::parameters::{CombiningRule, PetsBinaryRecord};
     use approx::assert_relative_eq;
+    use feos_core::parameter::{BinaryRecord, IdentifierOption, Parameter};
     use ndarray::arr1;
+    use std::fs;
 
     #[test]
     fn mix() {
         let c1 = Dispersion {
             parameters: argon_parameters(),
+            options: PetsOptions::default(),
         };
         let c2 = Dispersion {
             parameters: krypton_parameters(),
+            options: PetsOptions::default(),
         };
         let c12 = Dispersion {
             parameters: argon_krypton_parameters(),
+            options: PetsOptions::default(),
         };
         let t = 250.0;
         let v = 2.5e28;
@@ -109,4 +141,184 @@ mod tests {
         assert_relative_eq!(a1, a1m, epsilon = 1e-14);
         assert_relative_eq!(a2, a2m, epsilon = 1e-14);
     }
+
+    #[test]
+    fn temperature_dependent_k_ij() {
+        let pure_records = argon_krypton_parameters().pure_records.clone();
+        let record: PetsBinaryRecord = serde_json::from_str(r#"{"k_ij": 0.05}"#).unwrap();
+        let c_const = Dispersion {
+            parameters: Rc::new(PetsParameters::new_binary(
+                pure_records.clone(),
+                Some(record),
+            )),
+            options: PetsOptions::default(),
+        };
+        let t = 250.0;
+        let c_t = Dispersion {
+            parameters: Rc::new(PetsParameters::new_binary(
+                pure_records,
+                Some(PetsBinaryRecord::new(0.01, 0.04 * t, 0.0)),
+            )),
+            options: PetsOptions::default(),
+        };
+        let s = StateHD::new(t, 2.5e28, arr1(&[0.4, 0.6]));
+        assert_relative_eq!(
+            c_const.helmholtz_energy(&s),
+            c_t.helmholtz_energy(&s),
+            epsilon = 1e-14
+        );
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn temperature_independent_epsilon_k_ij() {
+        let pure_records = argon_krypton_parameters().pure_records.clone();
+        let parameters =
+            PetsParameters::new_binary(pure_records, Some(PetsBinaryRecord::from(0.05)));
+        assert_relative_eq!(
+            parameters.epsilon_k_ij,
+            parameters.epsilon_k_ij(250.0),
+            epsilon = 1e-12
+        );
+        let parameters = parameters.with_combining_rule(CombiningRule::FenderHalsey);
+        assert_relative_eq!(
+            parameters.epsilon_k_ij,
+            parameters.epsilon_k_ij(250.0),
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn mix_combining_rules() {
+        let t = 250.0;
+        let v = 2.5e28;
+        let n = 1.0;
+        let a1 = Dispersion {
+            parameters: argon_parameters(),
+            options: PetsOptions::default(),
+        }
+        .helmholtz_energy(&StateHD::new(t, v, arr1(&[n])));
+        for combining_rule in [
+            CombiningRule::LorentzBerthelot,
+            CombiningRule::Kong,
+            CombiningRule::WaldmanHagler,
+            CombiningRule::FenderHalsey,
+        ] {
+            let parameters = Rc::new(
+                (*argon_krypton_parameters())
+                    .clone()
+                    .with_combining_rule(combining_rule),
+            );
+            let a1m = Dispersion {
+                parameters: parameters.clone(),
+                options: PetsOptions::default(),
+            }
+            .helmholtz_energy(&StateHD::new(t, v, arr1(&[n, 0.0])));
+            assert_relative_eq!(a1, a1m, epsilon = 1e-14);
+            assert_relative_eq!(parameters.sigma_ij[[0, 0]], 3.405, epsilon = 1e-12);
+            assert_relative_eq!(parameters.e_k_ij[[0, 0]], 119.8, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn cross_parameters() {
+        let pure_records = argon_krypton_parameters().pure_records.clone();
+        let l_ij = 0.02;
+        let parameters =
+            PetsParameters::new_binary(pure_records, Some(PetsBinaryRecord::new(0.0, 0.0, l_ij)));
+        let (s1, s2, e1, e2) = (3.405f64, 3.63f64, 119.8f64, 163.1f64);
+        assert_relative_eq!(
+            parameters.sigma_ij[[0, 1]],
+            (1.0 - l_ij) * 0.5 * (s1 + s2),
+            epsilon = 1e-12
+        );
+
+        let parameters = parameters.with_combining_rule(CombiningRule::WaldmanHagler);
+        let s6 = 0.5 * (s1.powi(6) + s2.powi(6));
+        assert_relative_eq!(
+            parameters.sigma_ij[[0, 1]],
+            (1.0 - l_ij) * s6.powf(1.0 / 6.0),
+            epsilon = 1e-12
+        );
+        assert_relative_eq!(
+            parameters.e_k_ij[[1, 0]],
+            (e1 * e2).sqrt() * (s1 * s2).powi(3) / s6,
+            epsilon = 1e-12
+        );
+
+        let parameters = parameters.with_combining_rule(CombiningRule::FenderHalsey);
+        assert_relative_eq!(
+            parameters.e_k_ij[[0, 1]],
+            2.0 * e1 * e2 / (e1 + e2),
+            epsilon = 1e-12
+        );
+
+        // the combining rule is preserved for subsets
+        let parameters = parameters.with_combining_rule(CombiningRule::Kong);
+        let subset = parameters.subset(&[1, 0]);
+        assert_eq!(subset.combining_rule, CombiningRule::Kong);
+        assert_relative_eq!(
+            subset.e_k_ij[[0, 1]],
+            parameters.e_k_ij[[0, 1]],
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn combining_rule_from_json() {
+        let records = argon_krypton_parameters().pure_records.clone();
+        let binary = vec![BinaryRecord::new(
+            records[0].identifier.clone(),
+            records[1].identifier.clone(),
+            PetsBinaryRecord::new(0.0, 0.0, 0.02).with_combining_rule(CombiningRule::Kong),
+        )];
+        let dir = std::env::temp_dir();
+        let file_pure = dir.join("pets_combining_rule_pure.json");
+        let file_binary = dir.join("pets_combining_rule_binary.json");
+        fs::write(&file_pure, serde_json::to_string(&records).unwrap()).unwrap();
+        fs::write(&file_binary, serde_json::to_string(&binary).unwrap()).unwrap();
+        let parameters = PetsParameters::from_json(
+            vec!["krypton", "argon"],
+            &file_pure,
+            Some(&file_binary),
+            IdentifierOption::Name,
+        )
+        .unwrap();
+        fs::remove_file(file_pure).unwrap();
+        fs::remove_file(file_binary).unwrap();
+
+        let expected = PetsParameters::new_binary(
+            vec![records[1].clone(), records[0].clone()],
+            Some(PetsBinaryRecord::new(0.0, 0.0, 0.02)),
+        )
+        .with_combining_rule(CombiningRule::Kong);
+        assert_eq!(parameters.combining_rule, CombiningRule::LorentzBerthelot);
+        assert_relative_eq!(parameters.sigma_ij, expected.sigma_ij, epsilon = 1e-12);
+        assert_relative_eq!(parameters.e_k_ij, expected.e_k_ij, epsilon = 1e-12);
+
+        // the combining rule of the binary record is kept for subsets
+        let subset = parameters.subset(&[1, 0]);
+        assert_relative_eq!(
+            subset.sigma_ij[[0, 1]],
+            expected.sigma_ij[[0, 1]],
+            epsilon = 1e-12
+        );
+
+        // and replaced by the combining rule of the parameters
+        let parameters = parameters.with_combining_rule(CombiningRule::LorentzBerthelot);
+        assert_relative_eq!(
+            parameters.sigma_ij[[0, 1]],
+            0.98 * 0.5 * (3.405 + 3.63),
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn binary_record_without_k_ij() {
+        let record: PetsBinaryRecord =
+            serde_json::from_str(r#"{"l_ij": 0.02, "combining_rule": "Kong"}"#).unwrap();
+        assert_eq!(record.k_ij, 0.0);
+        assert_eq!(record.l_ij, 0.02);
+        assert_eq!(record.combining_rule, Some(CombiningRule::Kong));
+    }
 }
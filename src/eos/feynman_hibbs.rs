@@ -0,0 +1,333 @@
+use super::mie::mie_prefactor;
+use crate::parameters::PetsParameters;
+use ndarray::*;
+use num_dual::DualNum;
+
+/// ħ² / (k_B u Å²) in units of Kelvin (with the molar weight in g/mol).
+const HBAR2_KB: f64 =
+    1.054571817e-34 * 1.054571817e-34 / (1.380649e-23 * 1e-3 / 6.02214076e23 * 1e-20);
+
+const MAX_ITER: usize = 50;
+const TOL: f64 = 1e-14;
+
+/// Newton iteration for the root of `f` (returning the function value and its derivative).
+///
+/// After the convergence of the real part, additional steps are
+/// taken to converge the derivatives of the root.
+fn newton<D: DualNum<f64>, F: Fn(D) -> (D, D)>(mut x: D, f: F) -> D {
+    let mut converged = None;
+    for k in 0..MAX_ITER {
+        let (f, df) = f(x);
+        let dx = f / df;
+        x -= dx;
+        match converged {
+            Some(c) if k >= c + 3 => break,
+            None if dx.re().abs() < TOL => converged = Some(k),
+            _ => (),
+        }
+    }
+    x
+}
+
+/// Ratios `(sigma_eff / sigma, epsilon_eff / epsilon)` of the first-order
+/// Feynman-Hibbs Mie n-6 potential with the quantum parameter
+/// `lambda = ħ² / (24 mu k_B T sigma²)`.
+fn feynman_hibbs_factors<D: DualNum<f64>>(lambda: D, n: f64) -> (D, D) {
+    // with x = sigma / r, the potential is
+    // u / (C epsilon) = x^n (1 + n (n - 1) lambda x²) - x^6 (1 + 30 lambda x²)
+    let q = lambda * (n * (n - 1.0));
+    let u = |x: D| {
+        let x2 = x * x;
+        let x6 = x2 * x2 * x2;
+        x.powf(n) * (q * x2 + 1.0) - x6 * (lambda * x2 * 30.0 + 1.0)
+    };
+
+    // zero of the potential
+    let x0 = newton(D::one(), |x| {
+        let x2 = x * x;
+        let xn6 = x.powf(n - 6.0);
+        (
+            xn6 * (q * x2 + 1.0) - lambda * x2 * 30.0 - 1.0,
+            xn6 / x * (n - 6.0) + q * xn6 * x * (n - 4.0) - lambda * x * 60.0,
+        )
+    });
+
+    // minimum of the potential
+    let x_min = newton(D::from((6.0 / n).powf(1.0 / (n - 6.0))), |x| {
+        let x2 = x * x;
+        let xn6 = x.powf(n - 6.0);
+        (
+            xn6 * n + q * xn6 * x2 * (n + 2.0) - lambda * x2 * 240.0 - 6.0,
+            xn6 / x * (n * (n - 6.0)) + q * xn6 * x * ((n + 2.0) * (n - 4.0)) - lambda * x * 480.0,
+        )
+    });
+
+    (x0.recip(), -u(x_min) * mie_prefactor(n))
+}
+
+/// Quantum-corrected `(sigma, epsilon_k)` of an interaction with the
+/// reduced molar weight `mu` in g/mol and the repulsive exponent `n`.
+fn feynman_hibbs_parameters<D: DualNum<f64>>(
+    temperature: D,
+    sigma: f64,
+    epsilon_k: D,
+    mu: f64,
+    n: f64,
+) -> (D, D) {
+    let lambda = temperature.recip() * (HBAR2_KB / (24.0 * mu * sigma * sigma));
+    let (f_sigma, f_epsilon) = feynman_hibbs_factors(lambda, n);
+    (f_sigma * sigma, f_epsilon * epsilon_k)
+}
+
+impl PetsParameters {
+    /// Effective segment diameters and energy parameters `(sigma, epsilon_k)`
+    /// at the given temperature.
+    ///
+    /// Without the Feynman-Hibbs correction, these are the parameters of the components.
+    pub fn effective_parameters<D: DualNum<f64>>(
+        &self,
+        temperature: D,
+        feynman_hibbs: bool,
+    ) -> (Array1<D>, Array1<D>) {
+        if !feynman_hibbs {
+            return (self.sigma.mapv(D::from), self.epsilon_k.mapv(D::from));
+        }
+        let n = self.sigma.len();
+        let mut sigma = Array1::zeros(n);
+        let mut epsilon_k = Array1::zeros(n);
+        for i in 0..n {
+            let (s, e) = feynman_hibbs_parameters(
+                temperature,
+                self.sigma[i],
+                D::from(self.epsilon_k[i]),
+                0.5 * self.molarweight[i],
+                self.lambda_r[i],
+            );
+            sigma[i] = s;
+            epsilon_k[i] = e;
+        }
+        (sigma, epsilon_k)
+    }
+
+    /// Effective cross parameters `(sigma_ij, epsilon_k_ij)` at the given temperature,
+    /// including the binary interaction parameters.
+    pub fn effective_cross_parameters<D: DualNum<f64>>(
+        &self,
+        temperature: D,
+        feynman_hibbs: bool,
+    ) -> (Array2<D>, Array2<D>) {
+        let epsilon_k_ij = self.epsilon_k_ij(temperature);
+        if !feynman_hibbs {
+            return (self.sigma_ij.mapv(D::from), epsilon_k_ij);
+        }
+        let n = self.sigma.len();
+        let mut sigma = Array2::zeros((n, n));
+        let mut epsilon_k = Array2::zeros((n, n));
+        for i in 0..n {
+            for j in 0..n {
+                let (mw_i, mw_j) = (self.molarweight[i], self.molarweight[j]);
+                let (s, e) = feynman_hibbs_parameters(
+                    temperature,
+                    self.sigma_ij[(i, j)],
+                    epsilon_k_ij[(i, j)],
+                    mw_i * mw_j / (mw_i + mw_j),
+                    self.lambda_r_ij(i, j),
+                );
+                sigma[(i, j)] = s;
+                epsilon_k[(i, j)] = e;
+            }
+        }
+        (sigma, epsilon_k)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eos::association::Association;
+    use crate::eos::polar::{Dipole, DipoleQuadrupole, Quadrupole};
+    use crate::eos::{Pets, PetsOptions};
+    use crate::parameters::utils::{argon_krypton_parameters, argon_parameters, map_model_records};
+    use crate::PetsFunctional;
+    use approx::assert_relative_eq;
+    use feos_core::parameter::Parameter;
+    use feos_core::{Contributions, HelmholtzEnergyDual, State, StateHD};
+    use feos_dft::fundamental_measure_theory::FMTVersion;
+    use ndarray::arr1;
+    use num_dual::Dual64;
+    use quantity::si::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn first_order_limit() {
+        // sigma_eff / sigma = 1 + (n (n - 1) - 30) / (n - 6) lambda + O(lambda²)
+        let lambda = 1e-8;
+        for n in [12.0, 10.0] {
+            let (f_sigma, f_epsilon) = feynman_hibbs_factors(lambda, n);
+            assert_relative_eq!(
+                f_sigma,
+                1.0 + (n * (n - 1.0) - 30.0) / (n - 6.0) * lambda,
+                max_relative = 1e-12
+            );
+            assert!(f_epsilon < 1.0);
+            let (f_sigma, f_epsilon) = feynman_hibbs_factors(0.0, n);
+            assert_eq!(f_sigma, 1.0);
+            assert_relative_eq!(f_epsilon, 1.0, max_relative = 1e-14);
+        }
+    }
+
+    #[test]
+    fn classical_limit() {
+        // the classical limit is recovered for large molar weights
+        let mut parameters = argon_parameters();
+        let t = 30.0;
+        let (sigma, epsilon_k) = parameters.effective_parameters(t, true);
+        assert!(sigma[0] > parameters.sigma[0]);
+        assert!(epsilon_k[0] < parameters.epsilon_k[0]);
+        Rc::make_mut(&mut parameters).molarweight[0] = 1e12;
+        let (sigma, epsilon_k) = parameters.effective_parameters(t, true);
+        assert_relative_eq!(sigma[0], parameters.sigma[0], max_relative = 1e-10);
+        assert_relative_eq!(epsilon_k[0], parameters.epsilon_k[0], max_relative = 1e-10);
+    }
+
+    #[test]
+    fn cross_parameters() {
+        let parameters = argon_krypton_parameters();
+        let t = 100.0;
+        let (sigma, epsilon_k) = parameters.effective_parameters(t, true);
+        let (sigma_ij, epsilon_k_ij) = parameters.effective_cross_parameters(t, true);
+        for i in 0..2 {
+            assert_relative_eq!(sigma_ij[(i, i)], sigma[i], max_relative = 1e-14);
+            assert_relative_eq!(epsilon_k_ij[(i, i)], epsilon_k[i], max_relative = 1e-14);
+        }
+        let classical = argon_krypton_parameters();
+        let (sigma_ij, epsilon_k_ij) = classical.effective_cross_parameters(t, false);
+        assert_eq!(sigma_ij, classical.sigma_ij);
+        assert_eq!(epsilon_k_ij, classical.epsilon_k_ij(t));
+    }
+
+    #[test]
+    fn temperature_derivative() {
+        let parameters = argon_parameters();
+        let t = 20.0;
+        let h = 1e-5;
+        let (sigma, epsilon_k) = parameters.effective_parameters(Dual64::from(t).derive(), true);
+        let (sigma_p, epsilon_k_p) = parameters.effective_parameters(t + h, true);
+        let (sigma_m, epsilon_k_m) = parameters.effective_parameters(t - h, true);
+        assert_relative_eq!(
+            sigma[0].eps[0],
+            (sigma_p[0] - sigma_m[0]) / (2.0 * h),
+            max_relative = 1e-6
+        );
+        assert_relative_eq!(
+            epsilon_k[0].eps[0],
+            (epsilon_k_p[0] - epsilon_k_m[0]) / (2.0 * h),
+            max_relative = 1e-6
+        );
+    }
+
+    #[test]
+    fn options() {
+        let options = PetsOptions {
+            feynman_hibbs: true,
+            ..Default::default()
+        };
+        let t = 100.0 * KELVIN;
+        let v = 1e-4 * METER.powi(3);
+        for (parameters, n) in [
+            (argon_parameters(), arr1(&[1.0]) * MOL),
+            (argon_krypton_parameters(), arr1(&[0.3, 0.7]) * MOL),
+        ] {
+            let classical = Rc::new(Pets::new(parameters.clone()));
+            let eos = Rc::new(Pets::with_options(parameters.clone(), options).unwrap());
+            let func = Rc::new(
+                PetsFunctional::with_options(parameters, FMTVersion::WhiteBear, options).unwrap(),
+            );
+            let s_classical = State::new_nvt(&classical, t, v, &n).unwrap();
+            let s_eos = State::new_nvt(&eos, t, v, &n).unwrap();
+            let s_func = State::new_nvt(&func, t, v, &n).unwrap();
+            let p = Contributions::ResidualNvt;
+            assert!(s_eos.pressure(p) > s_classical.pressure(p));
+            assert_relative_eq!(
+                s_eos.pressure(Contributions::Total),
+                s_func.pressure(Contributions::Total),
+                max_relative = 1e-10
+            );
+            assert_relative_eq!(
+                s_eos.chemical_potential(Contributions::ResidualNvt),
+                s_func.chemical_potential(Contributions::ResidualNvt),
+                max_relative = 1e-10
+            );
+        }
+    }
+
+    #[test]
+    fn polar_and_association() {
+        // the quantum-corrected terms of pure components equal the classical
+        // terms evaluated with the effective parameters
+        let options = PetsOptions {
+            feynman_hibbs: true,
+            ..Default::default()
+        };
+        let t = 40.0;
+        let mixture = map_model_records(&argon_krypton_parameters(), |i, r| {
+            r.with_dipole_moment([1.5, 1.0][i])
+                .with_quadrupole_moment([2.0, 3.0][i])
+                .with_association([0.03, 0.01][i], [1000.0, 800.0][i], 1.0, 1.0)
+        });
+        for i in 0..2 {
+            let parameters = Rc::new(mixture.subset(&[i]));
+            let (sigma, epsilon_k) = parameters.effective_parameters(t, true);
+            let effective = map_model_records(&parameters, |_, mut r| {
+                r.sigma = sigma[0];
+                r.epsilon_k = epsilon_k[0];
+                r
+            });
+            let state = StateHD::new(t, 1e4, arr1(&[30.0]));
+            let quantum: Vec<Box<dyn HelmholtzEnergyDual<f64>>> = vec![
+                Box::new(Dipole {
+                    parameters: parameters.clone(),
+                    options,
+                }),
+                Box::new(Quadrupole {
+                    parameters: parameters.clone(),
+                    options,
+                }),
+                Box::new(DipoleQuadrupole {
+                    parameters: parameters.clone(),
+                    options,
+                }),
+                Box::new(Association {
+                    parameters,
+                    options,
+                }),
+            ];
+            let options = PetsOptions::default();
+            let classical: Vec<Box<dyn HelmholtzEnergyDual<f64>>> = vec![
+                Box::new(Dipole {
+                    parameters: effective.clone(),
+                    options,
+                }),
+                Box::new(Quadrupole {
+                    parameters: effective.clone(),
+                    options,
+                }),
+                Box::new(DipoleQuadrupole {
+                    parameters: effective.clone(),
+                    options,
+                }),
+                Box::new(Association {
+                    parameters: effective,
+                    options,
+                }),
+            ];
+            for (q, c) in quantum.iter().zip(classical.iter()) {
+                assert_relative_eq!(
+                    q.helmholtz_energy(&state),
+                    c.helmholtz_energy(&state),
+                    max_relative = 1e-12
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use super::hard_sphere::{hs_contact_value, zeta};
+use super::PetsOptions;
+use crate::parameters::PetsParameters;
+use feos_core::{HelmholtzEnergyDual, StateHD};
+use ndarray::*;
+use num_dual::DualNum;
+use std::fmt;
+use std::rc::Rc;
+
+/// Hard-chain contribution of the first-order thermodynamic perturbation theory (TPT1).
+#[derive(Debug, Clone)]
+pub struct HardChain {
+    pub parameters: Rc<PetsParameters>,
+    pub options: PetsOptions,
+}
+
+impl<D: DualNum<f64>> HelmholtzEnergyDual<D> for HardChain {
+    fn helmholtz_energy(&self, state: &StateHD<D>) -> D {
+        let p = &self.parameters;
+        let d = p.hs_diameter(state.temperature, &self.options);
+        let [_, _, zeta2, zeta3] = zeta(&p.m, &state.partial_density, &d);
+        let g = d.mapv(|d| hs_contact_value(zeta2, zeta3, d * 0.5));
+        -Zip::from(&p.m)
+            .and(&state.partial_density)
+            .and(&g)
+            .fold(D::zero(), |acc, &m, &rho, &g| {
+                acc + rho * (m - 1.0) * g.ln()
+            })
+            * state.volume
+    }
+}
+
+impl fmt::Display for HardChain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Hard Chain")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parameters::utils::{argon_parameters, map_model_records};
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn tpt1_dimer() {
+        let parameters = map_model_records(&argon_parameters(), |_, r| r.with_segments(2.0));
+        let t = 250.0;
+        let v = 1e5;
+        let n = 1000.0;
+        let a = HardChain {
+            parameters: parameters.clone(),
+            options: PetsOptions::default(),
+        }
+        .helmholtz_energy(&StateHD::new(t, v, arr1(&[n])));
+
+        // Carnahan-Starling contact value of the segments
+        let d = parameters.hs_diameter(t, &PetsOptions::default())[0];
+        let eta = std::f64::consts::FRAC_PI_6 * 2.0 * n / v * d.powi(3);
+        let g = (1.0 - 0.5 * eta) / (1.0 - eta).powi(3);
+        assert_relative_eq!(a, -n * g.ln(), max_relative = 1e-12);
+    }
+}
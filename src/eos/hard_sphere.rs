@@ -1,84 +1,173 @@
+use super::cutoff::shifted_cutoff;
+use super::mie::{barker_henderson_diameter, LJ_EXPONENT};
+use super::PetsOptions;
 use crate::parameters::PetsParameters;
 use feos_core::{HelmholtzEnergyDual, StateHD};
 use ndarray::*;
 use num_dual::DualNum;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::rc::Rc;
 
+/// Model of the temperature-dependent hard-sphere diameter.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "python", pyo3::pyclass)]
+pub enum HardSphereDiameter {
+    /// Correlation of the PeTS equation of state.
+    #[default]
+    Correlation,
+    /// Barker-Henderson diameter of the potential truncated and shifted at the
+    /// cutoff radius of the options, or of the unshifted potential with the tail correction.
+    BarkerHenderson,
+}
+
 impl PetsParameters {
-    pub fn hs_diameter<D: DualNum<f64>>(&self, temperature: D) -> Array1<D> {
+    /// Temperature-dependent hard-sphere diameters of the components
+    /// for the model and the corrections selected in `options`.
+    pub fn hs_diameter<D: DualNum<f64>>(&self, temperature: D, options: &PetsOptions) -> Array1<D> {
         let ti = temperature.recip() * -3.052785558;
+        let (sigma, epsilon_k) = self.effective_parameters(temperature, options.feynman_hibbs);
+        let cutoff = shifted_cutoff(options);
         Array::from_shape_fn(self.sigma.len(), |i| {
-            -((ti * self.epsilon_k[i]).exp() * 0.127112544 - 1.0) * self.sigma[i]
+            let n = self.lambda_r[i];
+            let t = temperature / epsilon_k[i];
+            if options.hs_diameter == HardSphereDiameter::BarkerHenderson {
+                return barker_henderson_diameter(n, t, cutoff) * sigma[i];
+            }
+            let d = -((ti * epsilon_k[i]).exp() * 0.127112544 - 1.0) * sigma[i];
+            if n == LJ_EXPONENT {
+                d
+            } else {
+                // the PeTS diameter is scaled with the Barker-Henderson diameters
+                // of the Mie and the Lennard-Jones potential
+                d * barker_henderson_diameter(n, t, cutoff)
+                    / barker_henderson_diameter(LJ_EXPONENT, t, cutoff)
+            }
         })
     }
 }
 
+/// Equation of state of the hard-sphere mixture used as reference fluid.
+///
+/// All models reduce to the Carnahan-Starling equation of state for pure components.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "python", pyo3::pyclass)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum HardSphereModel {
+    /// Boublík (1970) and Mansoori, Carnahan, Starling and Leland (1971).
+    #[default]
+    BMCSL,
+    /// Virial route with the contact values of
+    /// [Santos, Yuste and López de Haro (2002)](https://doi.org/10.1063/1.1502247)
+    /// that are linear in the size ratios, using the Carnahan-Starling pure fluid.
+    SYH,
+}
+
 #[derive(Debug, Clone)]
 pub struct HardSphere {
     pub parameters: Rc<PetsParameters>,
+    pub options: PetsOptions,
 }
 
 impl<D: DualNum<f64>> HelmholtzEnergyDual<D> for HardSphere {
     fn helmholtz_energy(&self, state: &StateHD<D>) -> D {
-        let d = self.parameters.hs_diameter(state.temperature);
-        let zeta = zeta(&state.partial_density, &d);
+        let p = &self.parameters;
+        let d = p.hs_diameter(state.temperature, &self.options);
+        let zeta = zeta(&p.m, &state.partial_density, &d);
         let frac_1mz3 = -(zeta[3] - 1.0).recip();
-        let zeta_23 = zeta_23(&state.molefracs, &d);
+        let zeta_23 = zeta_23(&p.m, &state.molefracs, &d);
+        let ln_1mz3 = (zeta[3] * (-1.0)).ln_1p();
 
-        state.volume * 6.0 / std::f64::consts::PI
-            * (zeta[1] * zeta[2] * frac_1mz3 * 3.0
-                + zeta[2].powi(2) * frac_1mz3.powi(2) * zeta_23
-                + (zeta[2] * zeta_23.powi(2) - zeta[0]) * (zeta[3] * (-1.0)).ln_1p())
+        let a = match self.options.hard_sphere_model {
+            HardSphereModel::BMCSL => {
+                zeta[1] * zeta[2] * frac_1mz3 * 3.0
+                    + zeta[2].powi(2) * frac_1mz3.powi(2) * zeta_23
+                    + (zeta[2] * zeta_23.powi(2) - zeta[0]) * ln_1mz3
+            }
+            HardSphereModel::SYH => {
+                // Z = 1 + (1 + 3 z1) eta / (1 - eta) + 2 (z1 + z2) eta (g_CS - 1 / (1 - eta))
+                // with z1 = zeta1 zeta2 / (zeta0 zeta3) and z2 = zeta2³ / (zeta0 zeta3²)
+                let z1 = zeta[1] * zeta_23;
+                let z2 = zeta[2] * zeta_23.powi(2);
+                -(zeta[0] + z1 * 3.0) * ln_1mz3
+                    + (z1 + z2) * (frac_1mz3.powi(2) * 0.5 + frac_1mz3 + ln_1mz3 * 2.0 - 1.5)
+            }
+        };
+        state.volume * 6.0 / std::f64::consts::PI * a
     }
 }
 
 impl fmt::Display for HardSphere {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Hard Sphere")
+        write!(f, "Hard Sphere ({:?})", self.options.hard_sphere_model)
     }
 }
 
-pub fn zeta<D: DualNum<f64>>(partial_density: &Array1<D>, diameter: &Array1<D>) -> [D; 4] {
+pub fn zeta<D: DualNum<f64>>(
+    m: &Array1<f64>,
+    partial_density: &Array1<D>,
+    diameter: &Array1<D>,
+) -> [D; 4] {
     let mut zeta: [D; 4] = [D::zero(), D::zero(), D::zero(), D::zero()];
     for i in 0..diameter.len() {
         for (k, z) in zeta.iter_mut().enumerate() {
-            *z += partial_density[i] * diameter[i].powi(k as i32) * (std::f64::consts::PI / 6.0);
+            *z += partial_density[i]
+                * diameter[i].powi(k as i32)
+                * (std::f64::consts::PI / 6.0 * m[i]);
         }
     }
     zeta
 }
 
-pub fn zeta_23<D: DualNum<f64>>(molefracs: &Array1<D>, diameter: &Array1<D>) -> D {
+pub fn zeta_23<D: DualNum<f64>>(m: &Array1<f64>, molefracs: &Array1<D>, diameter: &Array1<D>) -> D {
     let mut zeta: [D; 2] = [D::zero(), D::zero()];
     for i in 0..diameter.len() {
         for (k, z) in zeta.iter_mut().enumerate() {
-            *z += molefracs[i] * diameter[i].powi((k + 2) as i32);
+            *z += molefracs[i] * diameter[i].powi((k + 2) as i32) * m[i];
         }
     }
     zeta[0] / zeta[1]
 }
 
+/// Hard-sphere contact value of the radial distribution function between
+/// segments of the diameters `d_i` and `d_j` with `d_ij = d_i d_j / (d_i + d_j)`.
+pub(crate) fn hs_contact_value<D: DualNum<f64>>(zeta2: D, zeta3: D, d_ij: D) -> D {
+    let frac_1mz3 = -(zeta3 - 1.0).recip();
+    let z2d = zeta2 * d_ij;
+    frac_1mz3 + z2d * frac_1mz3.powi(2) * 3.0 + z2d.powi(2) * frac_1mz3.powi(3) * 2.0
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::eos::cutoff::PETS_CUTOFF;
+    use crate::eos::{Pets, PetsOptions};
     use crate::parameters::utils::{
         argon_krypton_parameters, argon_parameters, krypton_parameters,
     };
+    use crate::PetsFunctional;
     use approx::assert_relative_eq;
+    use feos_core::{Contributions, State};
+    use feos_dft::fundamental_measure_theory::FMTVersion;
     use ndarray::arr1;
+    use num_dual::Dual64;
+    use quantity::si::*;
+
+    fn hard_sphere(parameters: &Rc<PetsParameters>, model: HardSphereModel) -> HardSphere {
+        HardSphere {
+            parameters: parameters.clone(),
+            options: PetsOptions {
+                hard_sphere_model: model,
+                ..Default::default()
+            },
+        }
+    }
 
     #[test]
     fn mix() {
-        let c1 = HardSphere {
-            parameters: argon_parameters(),
-        };
-        let c2 = HardSphere {
-            parameters: krypton_parameters(),
-        };
-        let c12 = HardSphere {
-            parameters: argon_krypton_parameters(),
-        };
+        let c1 = hard_sphere(&argon_parameters(), HardSphereModel::BMCSL);
+        let c2 = hard_sphere(&krypton_parameters(), HardSphereModel::BMCSL);
+        let c12 = hard_sphere(&argon_krypton_parameters(), HardSphereModel::BMCSL);
         let t = 250.0;
         let v = 2.5e28;
         let n = 1.0;
@@ -92,4 +181,142 @@ mod tests {
         assert_relative_eq!(a1, a1m, epsilon = 1e-14);
         assert_relative_eq!(a2, a2m, epsilon = 1e-14);
     }
+
+    #[test]
+    fn pure_limit() {
+        // all models reduce to Carnahan-Starling: a = (4 eta - 3 eta²) / (1 - eta)²
+        let t = 250.0;
+        let v = 1e3;
+        for (parameters, n) in [
+            (argon_parameters(), arr1(&[20.0])),
+            (argon_krypton_parameters(), arr1(&[20.0, 0.0])),
+            (argon_krypton_parameters(), arr1(&[0.0, 20.0])),
+        ] {
+            let i = if n[0] > 0.0 { 0 } else { 1 };
+            let d = parameters.hs_diameter(t, &PetsOptions::default())[i];
+            let eta = std::f64::consts::FRAC_PI_6 * n.sum() / v * d.powi(3);
+            let cs = n.sum() * (4.0 * eta - 3.0 * eta * eta) / (1.0 - eta).powi(2);
+            for model in [HardSphereModel::BMCSL, HardSphereModel::SYH] {
+                let hs = hard_sphere(&parameters, model);
+                let a = hs.helmholtz_energy(&StateHD::new(t, v, n.clone()));
+                assert_relative_eq!(a, cs, max_relative = 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn syh_mixture() {
+        let t = 250.0;
+        let v = 1e3;
+        let n = arr1(&[10.0, 10.0]);
+        let parameters = argon_krypton_parameters();
+        let a = |model| {
+            hard_sphere(&parameters, model).helmholtz_energy(&StateHD::new(t, v, n.clone()))
+        };
+        let (bmcsl, syh) = (a(HardSphereModel::BMCSL), a(HardSphereModel::SYH));
+        assert!(bmcsl != syh);
+        // the second and third virial coefficients are exact for both models
+        assert_relative_eq!(bmcsl, syh, max_relative = 1e-3);
+
+        // pressure from the contact values via the virial route
+        let rho = n.sum() / v;
+        let x = &n / n.sum();
+        let d = parameters.hs_diameter(t, &PetsOptions::default());
+        let [_, _, z2, z3] = zeta(&parameters.m, &(&x * rho), &d);
+        let eta = z3;
+        let g_cs = (1.0 - 0.5 * eta) / (1.0 - eta).powi(3);
+        let mut b = 0.0;
+        for i in 0..2 {
+            for j in 0..2 {
+                let d_ij = 0.5 * (d[i] + d[j]);
+                let z_ij = d[i] * d[j] / d_ij * z2 / z3;
+                let g_ij = 1.0 / (1.0 - eta) + (g_cs - 1.0 / (1.0 - eta)) * z_ij;
+                b += x[i] * x[j] * d_ij.powi(3) * g_ij;
+            }
+        }
+        let z_virial = 1.0 + 2.0 / 3.0 * std::f64::consts::PI * rho * b;
+        let hs = hard_sphere(&parameters, HardSphereModel::SYH);
+        let a_v = hs
+            .helmholtz_energy(&StateHD::new(
+                Dual64::from(t),
+                Dual64::from(v).derive(),
+                n.mapv(Dual64::from),
+            ))
+            .eps[0];
+        assert_relative_eq!(1.0 - a_v * v / n.sum(), z_virial, max_relative = 1e-12);
+    }
+
+    #[test]
+    fn barker_henderson_diameter() {
+        // the correlation of PeTS deviates by less than 2.5 % from the
+        // Barker-Henderson diameter within the fitted temperature range
+        let parameters = argon_parameters();
+        let correlation = PetsOptions::default();
+        let exact = PetsOptions {
+            hs_diameter: HardSphereDiameter::BarkerHenderson,
+            ..Default::default()
+        };
+        for t_red in [0.6, 0.8, 1.0, 1.5, 2.0, 3.0, 5.0] {
+            let t = t_red * 119.8;
+            let d = parameters.hs_diameter(t, &correlation)[0];
+            let d_bh = parameters.hs_diameter(t, &exact)[0];
+            assert_relative_eq!(d, d_bh, max_relative = 2.5e-2);
+        }
+
+        // temperature derivative
+        let (t, h) = (100.0, 1e-4);
+        let d = parameters.hs_diameter(Dual64::from(t).derive(), &exact)[0];
+        assert_relative_eq!(
+            d.eps[0],
+            (parameters.hs_diameter(t + h, &exact)[0] - parameters.hs_diameter(t - h, &exact)[0])
+                / (2.0 * h),
+            max_relative = 1e-6
+        );
+    }
+
+    #[test]
+    fn barker_henderson_cutoff() {
+        let parameters = argon_parameters();
+        let (t, sigma, epsilon_k) = (150.0, 3.405, 119.8);
+        for (tail_correction, cutoff_bh) in [(false, PETS_CUTOFF), (true, f64::INFINITY)] {
+            let options = PetsOptions {
+                tail_correction,
+                hs_diameter: HardSphereDiameter::BarkerHenderson,
+                ..Default::default()
+            };
+            assert_relative_eq!(
+                parameters.hs_diameter(t, &options)[0],
+                super::barker_henderson_diameter(LJ_EXPONENT, t / epsilon_k, cutoff_bh) * sigma,
+                max_relative = 1e-14
+            );
+        }
+    }
+
+    #[test]
+    fn barker_henderson_bulk_consistency() {
+        let options = PetsOptions {
+            hs_diameter: HardSphereDiameter::BarkerHenderson,
+            ..Default::default()
+        };
+        let t = 120.0 * KELVIN;
+        let v = 1e-4 * METER.powi(3);
+        let n = arr1(&[0.3, 0.7]) * MOL;
+        let parameters = argon_krypton_parameters();
+        let correlation = Rc::new(Pets::new(parameters.clone()));
+        let eos = Rc::new(Pets::with_options(parameters.clone(), options).unwrap());
+        let func = Rc::new(
+            PetsFunctional::with_options(parameters, FMTVersion::WhiteBear, options).unwrap(),
+        );
+        let s_correlation = State::new_nvt(&correlation, t, v, &n).unwrap();
+        let s_eos = State::new_nvt(&eos, t, v, &n).unwrap();
+        let s_func = State::new_nvt(&func, t, v, &n).unwrap();
+        assert!(
+            s_eos.pressure(Contributions::Total) != s_correlation.pressure(Contributions::Total)
+        );
+        assert_relative_eq!(
+            s_eos.pressure(Contributions::Total),
+            s_func.pressure(Contributions::Total),
+            max_relative = 1e-10
+        );
+    }
 }
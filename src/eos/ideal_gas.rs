@@ -0,0 +1,545 @@
+use super::qspr::QSPR;
+use crate::parameters::{DipprRecord, NasaRecord, PetsIdealGasRecord, PetsParameters};
+use feos_core::joback::{Joback, JobackRecord};
+use feos_core::parameter::ParameterError;
+use feos_core::{EosError, EosResult, IdealGasContribution, IdealGasContributionDual};
+use ndarray::Array1;
+use num_dual::DualNum;
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+use std::fmt;
+use std::rc::Rc;
+
+const PLANCK: f64 = 6.62607015e-34;
+const KB: f64 = 1.380649e-23;
+const NAV: f64 = 6.02214076e23;
+const T0: f64 = 298.15;
+const P0: f64 = 1.0e5;
+const A3: f64 = 1e-30;
+
+/// Model of the ideal gas contribution.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "python", pyo3::pyclass)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum IdealGasModel {
+    /// Ideal gas records of the parameters if available, QSPR model otherwise.
+    #[default]
+    Automatic,
+    /// Translational degrees of freedom only (`c_p = 5/2 R`).
+    Monatomic,
+    /// QSPR model of the PeTS equation of state.
+    QSPR,
+    /// Ideal gas records of the parameters (Joback, NASA 7-coefficient or DIPPR 107).
+    Records,
+    /// Constant de Broglie wavelength (no caloric contribution).
+    Disabled,
+}
+
+/// Ideal gas contribution of monatomic molecules with the
+/// exact thermal de Broglie wavelength `h / sqrt(2 pi m k_B T)`.
+pub struct Monatomic {
+    pub parameters: Rc<PetsParameters>,
+}
+
+impl<D: DualNum<f64>> IdealGasContributionDual<D> for Monatomic {
+    fn de_broglie_wavelength(&self, temperature: D, components: usize) -> Array1<D> {
+        Array1::from_shape_fn(components, |i| {
+            let m = self.parameters.molarweight[i] * 1e-3 / NAV;
+            (temperature * (2.0 * PI * m * KB)).ln() * (-1.5) + 3.0 * (PLANCK * 1e10).ln()
+        })
+    }
+}
+
+impl fmt::Display for Monatomic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Ideal gas (monatomic)")
+    }
+}
+
+/// Ideal gas contribution with a constant thermal de Broglie wavelength.
+pub struct NoIdealGas;
+
+impl<D: DualNum<f64>> IdealGasContributionDual<D> for NoIdealGas {
+    fn de_broglie_wavelength(&self, _: D, components: usize) -> Array1<D> {
+        Array1::zeros(components)
+    }
+}
+
+impl fmt::Display for NoIdealGas {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Ideal gas (none)")
+    }
+}
+
+impl NasaRecord {
+    /// `(h - T s) / (R T)` at 1 bar.
+    fn reduced_gibbs_energy<D: DualNum<f64>>(&self, temperature: D) -> D {
+        let a = if temperature.re() < self.t_mid {
+            &self.low
+        } else {
+            &self.high
+        };
+        let t = temperature;
+        let h = t * (t * (t * (t * a[4] / 5.0 + a[3] / 4.0) + a[2] / 3.0) + a[1] / 2.0)
+            + a[0]
+            + t.recip() * a[5];
+        let s = t * (t * (t * (t * a[4] / 4.0 + a[3] / 3.0) + a[2] / 2.0) + a[1])
+            + t.ln() * a[0]
+            + a[6];
+        h - s
+    }
+}
+
+impl DipprRecord {
+    /// `h(T) - h(T0) - T (s(T) - s(T0))` in J/mol.
+    fn enthalpy_entropy<D: DualNum<f64>>(&self, temperature: D) -> D {
+        let t = temperature;
+        let mut h = (t - T0) * self.a;
+        let mut s = (t / T0).ln() * self.a;
+        if self.b != 0.0 {
+            let x = t.recip() * self.c;
+            let x0 = self.c / T0;
+            let coth = x.tanh().recip();
+            let coth0 = x0.tanh().recip();
+            h += (coth - coth0) * self.b * self.c;
+            s += (x * coth - x.sinh().ln() - x0 * coth0 + x0.sinh().ln()) * self.b;
+        }
+        if self.d != 0.0 {
+            let y = t.recip() * self.e;
+            let y0 = self.e / T0;
+            h -= (y.tanh() - y0.tanh()) * self.d * self.e;
+            s += (y.cosh().ln() - y * y.tanh() - y0.cosh().ln() + y0 * y0.tanh()) * self.d;
+        }
+        h - t * s
+    }
+}
+
+/// Ideal gas contribution from the ideal gas records of the components.
+///
+/// The heat capacity correlations are integrated analytically. For NASA
+/// records, the enthalpy and entropy include the integration constants `a6`
+/// and `a7`; for Joback and DIPPR 107 records, both are zero at 298.15 K and 1 bar.
+pub struct IdealGasRecords {
+    pub records: Vec<PetsIdealGasRecord>,
+    joback: Joback,
+}
+
+impl IdealGasRecords {
+    pub fn new(records: Vec<PetsIdealGasRecord>) -> Self {
+        let joback = Joback::new(
+            records
+                .iter()
+                .map(|r| match r {
+                    PetsIdealGasRecord::Joback(joback) => joback.clone(),
+                    _ => JobackRecord::default(),
+                })
+                .collect(),
+        );
+        Self { records, joback }
+    }
+}
+
+impl<D: DualNum<f64>> IdealGasContributionDual<D> for IdealGasRecords {
+    fn de_broglie_wavelength(&self, temperature: D, components: usize) -> Array1<D> {
+        let mut res = self.joback.de_broglie_wavelength(temperature, components);
+        let f = (temperature * KB / (P0 * A3)).ln();
+        for (r, record) in res.iter_mut().zip(self.records.iter()) {
+            match record {
+                PetsIdealGasRecord::Joback(_) => (),
+                PetsIdealGasRecord::Nasa { nasa } => {
+                    *r = nasa.reduced_gibbs_energy(temperature) + f;
+                }
+                PetsIdealGasRecord::Dippr { dippr } => {
+                    *r = dippr.enthalpy_entropy(temperature) / (temperature * KB * NAV) + f;
+                }
+            }
+        }
+        res
+    }
+}
+
+impl fmt::Display for IdealGasRecords {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Ideal gas (records)")
+    }
+}
+
+#[allow(clippy::upper_case_acronyms)]
+pub(crate) enum IdealGasContributions {
+    Monatomic(Monatomic),
+    QSPR(QSPR),
+    Records(IdealGasRecords),
+    Disabled(NoIdealGas),
+}
+
+impl IdealGasContributions {
+    /// Ideal gas contribution of the selected model.
+    ///
+    /// Returns an error if the records model is selected without
+    /// `ideal_gas_records` in the parameters.
+    pub(crate) fn new(parameters: &Rc<PetsParameters>, model: IdealGasModel) -> EosResult<Self> {
+        let parameters = parameters.clone();
+        Ok(match model {
+            IdealGasModel::Automatic => match parameters.ideal_gas_records.clone() {
+                Some(records) => Self::Records(IdealGasRecords::new(records)),
+                None => Self::QSPR(QSPR::new(&parameters)),
+            },
+            IdealGasModel::Monatomic => Self::Monatomic(Monatomic { parameters }),
+            IdealGasModel::QSPR => Self::QSPR(QSPR::new(&parameters)),
+            IdealGasModel::Records => {
+                let records = parameters.ideal_gas_records.clone().ok_or_else(|| {
+                    EosError::ParameterError(ParameterError::IncompatibleParameters(
+                        "The records ideal gas model requires ideal gas records of all components."
+                            .into(),
+                    ))
+                })?;
+                Self::Records(IdealGasRecords::new(records))
+            }
+            IdealGasModel::Disabled => Self::Disabled(NoIdealGas),
+        })
+    }
+
+    pub(crate) fn as_dyn(&self) -> &dyn IdealGasContribution {
+        match self {
+            Self::Monatomic(monatomic) => monatomic,
+            Self::QSPR(qspr) => qspr,
+            Self::Records(records) => records,
+            Self::Disabled(none) => none,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eos::{Pets, PetsOptions};
+    use crate::parameters::utils::{argon_krypton_parameters, argon_parameters};
+    use crate::parameters::PetsRecord;
+    use crate::PetsFunctional;
+    use approx::assert_relative_eq;
+    use feos_core::parameter::{Identifier, Parameter, PureRecord};
+    use feos_core::{Contributions, State};
+    use feos_dft::fundamental_measure_theory::FMTVersion;
+    use ndarray::arr1;
+    use quantity::si::*;
+
+    #[test]
+    fn monatomic_heat_capacity() {
+        let options = PetsOptions {
+            ideal_gas: IdealGasModel::Monatomic,
+            ..Default::default()
+        };
+        let eos = Rc::new(Pets::with_options(argon_parameters(), options).unwrap());
+        let s = State::new_nvt(
+            &eos,
+            300.0 * KELVIN,
+            1e-1 * METER.powi(3),
+            &(arr1(&[1.0]) * MOL),
+        )
+        .unwrap();
+        assert_relative_eq!(
+            s.c_v(Contributions::IdealGas),
+            1.5 * RGAS,
+            max_relative = 1e-10
+        );
+        assert_relative_eq!(
+            s.c_p(Contributions::IdealGas),
+            2.5 * RGAS,
+            max_relative = 1e-10
+        );
+    }
+
+    #[test]
+    fn sackur_tetrode() {
+        // molar entropy of argon at 298.15 K and 1 bar (NIST: 154.8 J/mol/K)
+        let options = PetsOptions {
+            ideal_gas: IdealGasModel::Monatomic,
+            ..Default::default()
+        };
+        let parameters = argon_parameters();
+        let eos = Rc::new(Pets::with_options(parameters.clone(), options).unwrap());
+        let t = 298.15 * KELVIN;
+        let n = arr1(&[1.0]) * MOL;
+        let v = n.sum() * RGAS * t / BAR;
+        let s = State::new_nvt(&eos, t, v, &n).unwrap();
+        // Sackur-Tetrode equation
+        let m = parameters.molarweight[0] * 1e-3 / super::NAV;
+        let lambda = super::PLANCK / (2.0 * PI * m * super::KB * 298.15).sqrt();
+        let s_st = 2.5 - (lambda.powi(3) * 1e5 / (super::KB * 298.15)).ln();
+        assert_relative_eq!(
+            s.molar_entropy(Contributions::IdealGas),
+            s_st * RGAS,
+            max_relative = 1e-8
+        );
+        assert_relative_eq!(
+            s.molar_entropy(Contributions::IdealGas),
+            154.8 * JOULE / MOL / KELVIN,
+            max_relative = 1e-3
+        );
+    }
+
+    #[test]
+    fn eos_functional_consistency() {
+        let t = 150.0 * KELVIN;
+        let v = 1e-3 * METER.powi(3);
+        let records = |parameters: Rc<PetsParameters>| {
+            let mut parameters = parameters.as_ref().clone();
+            let records: [PetsIdealGasRecord; 3] = [
+                JobackRecord::new(20.8, 0.0, 0.0, 0.0, 0.0).into(),
+                methane_nasa().into(),
+                methane_dippr().into(),
+            ];
+            parameters.ideal_gas_records = Some(records[..parameters.sigma.len()].to_vec());
+            Rc::new(parameters)
+        };
+        for (parameters, n) in [
+            (argon_parameters(), arr1(&[1.0]) * MOL),
+            (argon_krypton_parameters(), arr1(&[0.3, 0.7]) * MOL),
+        ] {
+            let parameters = records(parameters);
+            for ideal_gas in [
+                IdealGasModel::Automatic,
+                IdealGasModel::Monatomic,
+                IdealGasModel::QSPR,
+                IdealGasModel::Records,
+                IdealGasModel::Disabled,
+            ] {
+                let options = PetsOptions {
+                    ideal_gas,
+                    ..Default::default()
+                };
+                let eos = Rc::new(Pets::with_options(parameters.clone(), options).unwrap());
+                let func = Rc::new(
+                    PetsFunctional::with_options(
+                        parameters.clone(),
+                        FMTVersion::WhiteBear,
+                        options,
+                    )
+                    .unwrap(),
+                );
+                let s_eos = State::new_nvt(&eos, t, v, &n).unwrap();
+                let s_func = State::new_nvt(&func, t, v, &n).unwrap();
+                assert_relative_eq!(
+                    s_eos.molar_entropy(Contributions::Total),
+                    s_func.molar_entropy(Contributions::Total),
+                    max_relative = 1e-10
+                );
+                assert_relative_eq!(
+                    s_eos.c_p(Contributions::Total),
+                    s_func.c_p(Contributions::Total),
+                    max_relative = 1e-10
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn automatic_model() {
+        let t = 300.0 * KELVIN;
+        let v = 1e-1 * METER.powi(3);
+        let n = arr1(&[1.0]) * MOL;
+        let entropy = |parameters: &Rc<PetsParameters>, ideal_gas| {
+            let options = PetsOptions {
+                ideal_gas,
+                ..Default::default()
+            };
+            let eos = Rc::new(Pets::with_options(parameters.clone(), options).unwrap());
+            State::new_nvt(&eos, t, v, &n)
+                .unwrap()
+                .molar_entropy(Contributions::IdealGas)
+        };
+
+        // QSPR without records, records otherwise
+        let mut parameters = argon_parameters();
+        assert_eq!(
+            entropy(&parameters, IdealGasModel::Automatic),
+            entropy(&parameters, IdealGasModel::QSPR)
+        );
+        let mut records = parameters.as_ref().clone();
+        records.ideal_gas_records = Some(vec![methane_nasa().into()]);
+        parameters = Rc::new(records);
+        assert_eq!(
+            entropy(&parameters, IdealGasModel::Automatic),
+            entropy(&parameters, IdealGasModel::Records)
+        );
+        assert!(
+            entropy(&parameters, IdealGasModel::Automatic)
+                != entropy(&parameters, IdealGasModel::QSPR)
+        );
+    }
+
+    #[test]
+    fn missing_records() {
+        let options = PetsOptions {
+            ideal_gas: IdealGasModel::Records,
+            ..Default::default()
+        };
+        assert!(Pets::with_options(argon_parameters(), options).is_err());
+        assert!(
+            PetsFunctional::with_options(argon_parameters(), FMTVersion::WhiteBear, options)
+                .is_err()
+        );
+    }
+
+    /// Methane (GRI-Mech 3.0).
+    fn methane_nasa() -> NasaRecord {
+        NasaRecord::new(
+            1000.0,
+            [
+                5.14987613e+00,
+                -1.36709788e-02,
+                4.91800599e-05,
+                -4.84743026e-08,
+                1.66693956e-11,
+                -1.02466476e+04,
+                -4.64130376e+00,
+            ],
+            [
+                7.48514950e-02,
+                1.33909467e-02,
+                -5.73285809e-06,
+                1.22292535e-09,
+                -1.01815230e-13,
+                -9.46834459e+03,
+                1.84373180e+01,
+            ],
+        )
+    }
+
+    /// Methane (DIPPR 107, converted to J/(mol K)).
+    fn methane_dippr() -> DipprRecord {
+        DipprRecord::new(33.298, 79.933, 2086.9, 41.602, 991.96)
+    }
+
+    fn methane_parameters(record: PetsIdealGasRecord) -> Rc<PetsParameters> {
+        let record = PureRecord::new(
+            Identifier::new("", Some("methane"), None, None, None, None),
+            16.043,
+            PetsRecord::new(3.6, 150.0, None, None, None),
+            Some(record),
+        );
+        Rc::new(PetsParameters::new_pure(record))
+    }
+
+    fn ideal_gas_state(parameters: Rc<PetsParameters>, t: SINumber) -> State<SIUnit, Pets> {
+        let options = PetsOptions {
+            ideal_gas: IdealGasModel::Records,
+            ..Default::default()
+        };
+        let eos = Rc::new(Pets::with_options(parameters, options).unwrap());
+        let n = arr1(&[1.0]) * MOL;
+        let v = RGAS * t * MOL / BAR;
+        State::new_nvt(&eos, t, v, &n).unwrap()
+    }
+
+    #[test]
+    fn json() {
+        let json = r#"[
+            {"a": 20.8, "b": 0.0, "c": 0.0, "d": 0.0, "e": 0.0},
+            {"nasa": {"t_mid": 1000.0, "low": [2.5, 0, 0, 0, 0, 0, 0], "high": [2.5, 0, 0, 0, 0, 0, 0]}},
+            {"dippr": {"a": 20.8, "b": 0.0, "c": 1.0, "d": 0.0, "e": 1.0}}
+        ]"#;
+        let records: Vec<PetsIdealGasRecord> = serde_json::from_str(json).unwrap();
+        assert!(matches!(records[0], PetsIdealGasRecord::Joback(_)));
+        assert!(matches!(records[1], PetsIdealGasRecord::Nasa { .. }));
+        assert!(matches!(records[2], PetsIdealGasRecord::Dippr { .. }));
+
+        let json = serde_json::to_string(&records).unwrap();
+        let records: Vec<PetsIdealGasRecord> = serde_json::from_str(&json).unwrap();
+        assert!(matches!(records[1], PetsIdealGasRecord::Nasa { .. }));
+        assert!(matches!(records[2], PetsIdealGasRecord::Dippr { .. }));
+    }
+
+    #[test]
+    fn nasa_methane() {
+        // c_p and s (JANAF) and the enthalpy of formation (GRI-Mech) at 298.15 K and 1 bar
+        let parameters = methane_parameters(methane_nasa().into());
+        let s = ideal_gas_state(parameters.clone(), 298.15 * KELVIN);
+        assert_relative_eq!(
+            s.c_p(Contributions::IdealGas),
+            35.69 * JOULE / MOL / KELVIN,
+            max_relative = 1e-3
+        );
+        assert_relative_eq!(
+            s.molar_entropy(Contributions::IdealGas),
+            186.25 * JOULE / MOL / KELVIN,
+            max_relative = 1e-3
+        );
+        assert_relative_eq!(
+            s.molar_enthalpy(Contributions::IdealGas),
+            -74.60 * KILO * JOULE / MOL,
+            max_relative = 1e-3
+        );
+
+        // both temperature ranges
+        for (t, cp) in [(200.0, 33.51), (600.0, 52.74), (1500.0, 90.44)] {
+            let s = ideal_gas_state(parameters.clone(), t * KELVIN);
+            assert_relative_eq!(
+                s.c_p(Contributions::IdealGas),
+                cp * JOULE / MOL / KELVIN,
+                max_relative = 5e-3
+            );
+        }
+    }
+
+    #[test]
+    fn dippr_methane() {
+        let record = methane_dippr();
+        let parameters = methane_parameters(record.clone().into());
+        let c_p = |t: f64| {
+            let x = record.c / t;
+            let y = record.e / t;
+            record.a + record.b * (x / x.sinh()).powi(2) + record.d * (y / y.cosh()).powi(2)
+        };
+
+        // enthalpy and entropy vanish at the reference state
+        let s = ideal_gas_state(parameters.clone(), 298.15 * KELVIN);
+        assert_relative_eq!(
+            s.molar_enthalpy(Contributions::IdealGas),
+            0.0 * JOULE / MOL,
+            epsilon = 1e-8
+        );
+        assert_relative_eq!(
+            s.molar_entropy(Contributions::IdealGas),
+            0.0 * JOULE / MOL / KELVIN,
+            epsilon = 1e-8
+        );
+
+        // heat capacity, enthalpy and entropy compared to the
+        // correlation (trapezoidal rule) and JANAF
+        let n = 100000;
+        let mut h = 0.0;
+        let mut s = 0.0;
+        let mut t0 = 298.15;
+        for (t, cp) in [(400.0, 40.63), (1000.0, 71.80)] {
+            let dt = (t - t0) / n as f64;
+            for i in 0..n {
+                let (t1, t2) = (t0 + i as f64 * dt, t0 + (i + 1) as f64 * dt);
+                h += 0.5 * (c_p(t1) + c_p(t2)) * dt;
+                s += 0.5 * (c_p(t1) / t1 + c_p(t2) / t2) * dt;
+            }
+            t0 = t;
+            let state = ideal_gas_state(parameters.clone(), t * KELVIN);
+            assert_relative_eq!(
+                state.c_p(Contributions::IdealGas),
+                c_p(t) * JOULE / MOL / KELVIN,
+                max_relative = 1e-10
+            );
+            assert_relative_eq!(
+                state.c_p(Contributions::IdealGas),
+                cp * JOULE / MOL / KELVIN,
+                max_relative = 2e-2
+            );
+            assert_relative_eq!(
+                state.molar_enthalpy(Contributions::IdealGas),
+                h * JOULE / MOL,
+                max_relative = 1e-8
+            );
+            assert_relative_eq!(
+                state.molar_entropy(Contributions::IdealGas),
+                s * JOULE / MOL / KELVIN,
+                max_relative = 1e-8
+            );
+        }
+    }
+}
@@ -0,0 +1,266 @@
+use super::ideal_gas::{IdealGasContributions, IdealGasModel};
+use crate::parameters::PetsParameters;
+use feos_core::parameter::Parameter;
+use feos_core::{
+    EosResult, EquationOfState, HelmholtzEnergy, HelmholtzEnergyDual, IdealGasContribution,
+    MolarWeight, StateHD,
+};
+use ndarray::Array1;
+use num_dual::DualNum;
+use quantity::si::*;
+use std::fmt;
+use std::rc::Rc;
+
+/// Maximum reduced density `rho sigma³` of the full Lennard-Jones fluid.
+const MAX_DENSITY: f64 = 1.2;
+
+const GAMMA: f64 = 3.0;
+
+/// Coefficients of the modified Benedict-Webb-Rubin equation of state
+/// of Johnson, Zollweg and Gubbins (1993).
+const X: [f64; 32] = [
+    0.8623085097507421,
+    2.976218765822098,
+    -8.402230115796038,
+    0.1054136629203555,
+    -0.8564583828174598,
+    1.582759470107601,
+    0.7639421948305453,
+    1.753173414312048,
+    2.798291772190376e3,
+    -4.839422026085766e-2,
+    0.9963265197721935,
+    -3.698000291272493e1,
+    2.084012299434647e1,
+    8.305402124717285e1,
+    -9.574799715203068e2,
+    -1.477746229234994e2,
+    6.398607852471505e1,
+    1.603993673294834e1,
+    6.805916615864377e1,
+    -2.791293578795945e3,
+    -6.245128304568454,
+    -8.11683610495841e3,
+    1.488735559561229e1,
+    -1.059346754655084e4,
+    -1.131607632802822e2,
+    -8.867771540418822e3,
+    -3.986982844450543e1,
+    -4.689270299917261e3,
+    2.593535277438717e2,
+    -2.694523589434903e3,
+    -7.218487631550215e2,
+    1.721802063863269e2,
+];
+
+/// Residual Helmholtz energy per particle `A / (N epsilon)` of the full
+/// Lennard-Jones fluid at the reduced temperature `t` and reduced density `rho`.
+pub(crate) fn a_res_jzg<D: DualNum<f64>>(t: D, rho: D) -> D {
+    let x = &X;
+    let ti = t.recip();
+    let ti2 = ti * ti;
+    let ti3 = ti2 * ti;
+    let ti4 = ti2 * ti2;
+    let a = [
+        t * x[0] + t.sqrt() * x[1] + x[2] + ti * x[3] + ti2 * x[4],
+        t * x[5] + x[6] + ti * x[7] + ti2 * x[8],
+        t * x[9] + x[10] + ti * x[11],
+        D::from(x[12]),
+        ti * x[13] + ti2 * x[14],
+        ti * x[15],
+        ti * x[16] + ti2 * x[17],
+        ti2 * x[18],
+    ];
+    let b = [
+        ti2 * x[19] + ti3 * x[20],
+        ti2 * x[21] + ti4 * x[22],
+        ti2 * x[23] + ti3 * x[24],
+        ti2 * x[25] + ti4 * x[26],
+        ti2 * x[27] + ti3 * x[28],
+        ti2 * x[29] + ti3 * x[30] + ti4 * x[31],
+    ];
+
+    let rho2 = rho * rho;
+    let f = (-rho2 * GAMMA).exp();
+    let mut g = (-f + 1.0) / (2.0 * GAMMA);
+    let mut rho_k = D::one();
+    let mut a_res = b[0] * g;
+    for (k, &b) in b.iter().enumerate().skip(1) {
+        rho_k *= rho2;
+        g = -(f * rho_k - g * (2 * k) as f64) / (2.0 * GAMMA);
+        a_res += b * g;
+    }
+    let mut rho_i = D::one();
+    for (i, &a) in a.iter().enumerate() {
+        rho_i *= rho;
+        a_res += a * rho_i / (i + 1) as f64;
+    }
+    a_res
+}
+
+/// Residual Helmholtz energy of the full Lennard-Jones fluid according to
+/// Johnson, Zollweg and Gubbins (1993).
+///
+/// Mixtures are described with the van der Waals one-fluid theory.
+#[derive(Debug, Clone)]
+pub struct JohnsonZollwegGubbins {
+    pub parameters: Rc<PetsParameters>,
+}
+
+impl<D: DualNum<f64>> HelmholtzEnergyDual<D> for JohnsonZollwegGubbins {
+    fn helmholtz_energy(&self, state: &StateHD<D>) -> D {
+        let p = &self.parameters;
+        let n = p.sigma.len();
+        let epsilon_k_ij = p.epsilon_k_ij(state.temperature);
+        let x = &state.molefracs;
+
+        // van der Waals one-fluid mixing rules
+        let mut sigma3 = D::zero();
+        let mut epsilon_sigma3 = D::zero();
+        for i in 0..n {
+            for j in 0..n {
+                let s3 = x[i] * x[j] * p.sigma_ij[(i, j)].powi(3);
+                sigma3 += s3;
+                epsilon_sigma3 += s3 * epsilon_k_ij[(i, j)];
+            }
+        }
+        let epsilon_k = epsilon_sigma3 / sigma3;
+
+        let t = state.temperature / epsilon_k;
+        let rho = state.partial_density.sum() * sigma3;
+        a_res_jzg(t, rho) / t * state.moles.sum()
+    }
+}
+
+impl fmt::Display for JohnsonZollwegGubbins {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Johnson-Zollweg-Gubbins")
+    }
+}
+
+/// Equation of state of the full (untruncated) Lennard-Jones fluid.
+///
+/// The equation of state uses the same parameters as [Pets](super::Pets) but only
+/// the segment diameters, the energy parameters and the binary interaction parameters
+/// are considered.
+pub struct LennardJones {
+    parameters: Rc<PetsParameters>,
+    ideal_gas_model: IdealGasModel,
+    contributions: Vec<Box<dyn HelmholtzEnergy>>,
+    ideal_gas: IdealGasContributions,
+}
+
+impl LennardJones {
+    pub fn new(parameters: Rc<PetsParameters>) -> Self {
+        // the default ideal gas model is available for all parameters
+        Self::with_ideal_gas(parameters, IdealGasModel::default()).unwrap()
+    }
+
+    pub fn with_ideal_gas(
+        parameters: Rc<PetsParameters>,
+        ideal_gas_model: IdealGasModel,
+    ) -> EosResult<Self> {
+        let contributions: Vec<Box<dyn HelmholtzEnergy>> = vec![Box::new(JohnsonZollwegGubbins {
+            parameters: parameters.clone(),
+        })];
+        Ok(Self {
+            ideal_gas: IdealGasContributions::new(&parameters, ideal_gas_model)?,
+            parameters,
+            ideal_gas_model,
+            contributions,
+        })
+    }
+}
+
+impl EquationOfState for LennardJones {
+    fn components(&self) -> usize {
+        self.parameters.pure_records.len()
+    }
+
+    fn subset(&self, component_list: &[usize]) -> Self {
+        Self::with_ideal_gas(
+            Rc::new(self.parameters.subset(component_list)),
+            self.ideal_gas_model,
+        )
+        .expect("The subset contains the ideal gas records of the components.")
+    }
+
+    fn compute_max_density(&self, moles: &Array1<f64>) -> f64 {
+        MAX_DENSITY * moles.sum() / (self.parameters.sigma.mapv(|v| v.powi(3)) * moles).sum()
+    }
+
+    fn residual(&self) -> &[Box<dyn HelmholtzEnergy>] {
+        &self.contributions
+    }
+
+    fn ideal_gas(&self) -> &dyn IdealGasContribution {
+        self.ideal_gas.as_dyn()
+    }
+}
+
+impl MolarWeight<SIUnit> for LennardJones {
+    fn molar_weight(&self) -> SIArray1 {
+        self.parameters.molarweight.clone() * GRAM / MOL
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eos::Pets;
+    use crate::parameters::utils::{argon_krypton_parameters, argon_parameters};
+    use approx::assert_relative_eq;
+    use feos_core::{Contributions, PhaseEquilibrium, SolverOptions, State};
+    use ndarray::arr1;
+    use num_dual::Dual64;
+
+    #[test]
+    fn second_virial_coefficient() {
+        // B2 / sigma³ of the Lennard-Jones fluid at T* = 1
+        let a = a_res_jzg(Dual64::from(1.0), Dual64::from(0.0).derive());
+        assert_relative_eq!(a.eps[0], -5.3158, max_relative = 1e-3);
+    }
+
+    #[test]
+    fn critical_point() {
+        let parameters = argon_parameters();
+        let eos = Rc::new(LennardJones::new(parameters.clone()));
+        let cp = State::critical_point(&eos, None, None, SolverOptions::default()).unwrap();
+        let t = cp.temperature.to_reduced(119.8 * KELVIN).unwrap();
+        let rho = (cp.density * NAV * (3.405 * ANGSTROM).powi(3))
+            .into_value()
+            .unwrap();
+        assert_relative_eq!(t, 1.313, max_relative = 1e-2);
+        assert_relative_eq!(rho, 0.31, max_relative = 5e-2);
+
+        // the truncated and shifted potential has a lower critical temperature
+        let pets = Rc::new(Pets::new(parameters));
+        let cp = State::critical_point(&pets, None, None, SolverOptions::default()).unwrap();
+        assert!(cp.temperature.to_reduced(119.8 * KELVIN).unwrap() < 1.1);
+    }
+
+    #[test]
+    fn vapor_liquid_equilibrium() {
+        let eos = Rc::new(LennardJones::new(argon_parameters()));
+        let t = 0.7 * 119.8 * KELVIN;
+        let vle = PhaseEquilibrium::pure(&eos, t, None, SolverOptions::default()).unwrap();
+        let rho = vle.liquid().density * NAV * (3.405 * ANGSTROM).powi(3);
+        assert_relative_eq!(rho.into_value().unwrap(), 0.843, max_relative = 1e-2);
+    }
+
+    #[test]
+    fn mixture() {
+        // a mixture of identical components equals the pure component
+        let t = 150.0 * KELVIN;
+        let v = 1e-4 * METER.powi(3);
+        let mix = Rc::new(LennardJones::new(argon_krypton_parameters()));
+        let pure = Rc::new(mix.subset(&[0]));
+        let s_pure = State::new_nvt(&pure, t, v, &(arr1(&[1.0]) * MOL)).unwrap();
+        let s_mix = State::new_nvt(&mix, t, v, &(arr1(&[1.0, 0.0]) * MOL)).unwrap();
+        assert_relative_eq!(
+            s_pure.pressure(Contributions::Total),
+            s_mix.pressure(Contributions::Total),
+            max_relative = 1e-12
+        );
+    }
+}
@@ -0,0 +1,365 @@
+use super::cutoff::PETS_CUTOFF;
+use crate::parameters::PetsParameters;
+use ndarray::Array2;
+use num_dual::DualNum;
+
+/// Repulsive exponent of the Lennard-Jones potential.
+pub const LJ_EXPONENT: f64 = 12.0;
+
+// Gauss-Legendre quadrature with 20 points (positive nodes and weights).
+pub(crate) const GAUSS_LEGENDRE: [[f64; 2]; 10] = [
+    [0.07652652113349734, 0.15275338713072598],
+    [0.2277858511416451, 0.14917298647260377],
+    [0.37370608871541955, 0.14209610931838215],
+    [0.5108670019508271, 0.1316886384491765],
+    [0.636053680726515, 0.11819453196151831],
+    [0.7463319064601508, 0.10193011981724048],
+    [0.8391169718222189, 0.08327674157670474],
+    [0.912234428251326, 0.06267204833410904],
+    [0.9639719272779138, 0.04060142980038705],
+    [0.9931285991850949, 0.017614007139152264],
+];
+
+/// Prefactor of the Mie n-6 potential, 4 for the Lennard-Jones potential.
+pub(crate) fn mie_prefactor(n: f64) -> f64 {
+    n / (n - 6.0) * (n / 6.0).powf(6.0 / (n - 6.0))
+}
+
+/// Reduced Mie n-6 potential `u / epsilon` at the reduced distance `x = r / sigma`.
+pub(crate) fn u_mie(x: f64, n: f64) -> f64 {
+    mie_prefactor(n) * (x.powf(-n) - x.powi(-6))
+}
+
+/// Integral of `x^(2-k)` from 1 to `cutoff`.
+fn power_integral(k: f64, cutoff: f64) -> f64 {
+    (cutoff.powf(3.0 - k) - 1.0) / (3.0 - k)
+}
+
+/// First- and second-order mean-field integrals of the Mie n-6 potential
+/// truncated and shifted at `cutoff` (the full potential for an infinite cutoff).
+fn mean_field_integrals(n: f64, cutoff: f64) -> (f64, f64) {
+    let c = mie_prefactor(n);
+    let p = |k| power_integral(k, cutoff);
+    let j1 = c * (p(n) - p(6.0));
+    let j2 = c * c * (p(2.0 * n) - 2.0 * p(n + 6.0) + p(12.0));
+    if cutoff.is_infinite() {
+        return (j1, j2);
+    }
+    let u_c = u_mie(cutoff, n);
+    (j1 - u_c * p(0.0), j2 - 2.0 * u_c * j1 + u_c * u_c * p(0.0))
+}
+
+/// Reduced distance at which the Mie n-6 potential equals `u` (below its zero).
+fn inverse_repulsion(u: f64, n: f64, mut x: f64) -> f64 {
+    let c = mie_prefactor(n);
+    for _ in 0..20 {
+        let f = c * (x.powf(-n) - x.powi(-6)) - u;
+        let df = c * (6.0 * x.powi(-7) - n * x.powf(-n - 1.0));
+        x -= f / df;
+    }
+    x
+}
+
+/// Barker-Henderson diameter (in units of sigma) of the Mie n-6 potential
+/// at the reduced temperature `t = kT / epsilon`.
+///
+/// For a finite `cutoff`, the potential is shifted to 0 at the cutoff radius.
+pub(crate) fn barker_henderson_diameter<D: DualNum<f64>>(n: f64, t: D, cutoff: f64) -> D {
+    let c = mie_prefactor(n);
+    let u_c = if cutoff.is_finite() {
+        u_mie(cutoff, n)
+    } else {
+        0.0
+    };
+
+    // zero of the (shifted) potential
+    let x_s = if u_c == 0.0 {
+        1.0
+    } else {
+        inverse_repulsion(u_c, n, 1.0)
+    };
+
+    // below x0, the Boltzmann factor is negligible (u / kT > 50)
+    let u_max = 50.0 * t.re();
+    let x0 = inverse_repulsion(u_max + u_c, n, (c / u_max).powf(1.0 / n).min(1.0));
+
+    // integration of the Boltzmann factor from x0 to x_s
+    let h = 0.5 * (x_s - x0);
+    let integral = GAUSS_LEGENDRE.iter().fold(D::zero(), |acc, &[xi, w]| {
+        let boltzmann = |x: f64| (-t.recip() * (u_mie(x, n) - u_c)).exp();
+        acc + (boltzmann(x_s - h * (1.0 - xi)) + boltzmann(x_s - h * (1.0 + xi))) * (w * h)
+    });
+    -integral + x_s
+}
+
+impl PetsParameters {
+    /// Whether any component interacts with a Mie potential other than Lennard-Jones.
+    pub fn has_mie(&self) -> bool {
+        self.lambda_r.iter().any(|&n| n != LJ_EXPONENT)
+    }
+
+    /// Repulsive exponent of the cross interaction of components `i` and `j`.
+    pub fn lambda_r_ij(&self, i: usize, j: usize) -> f64 {
+        3.0 + ((self.lambda_r[i] - 3.0) * (self.lambda_r[j] - 3.0)).sqrt()
+    }
+
+    /// Factors of the first- and second-order dispersion terms for all pairs of components.
+    ///
+    /// The coefficients of PeTS are scaled with the mean-field integrals of
+    /// the Mie potential relative to those of the Lennard-Jones potential,
+    /// both truncated and shifted at [PETS_CUTOFF]. With `tail_correction`,
+    /// the difference to the full Mie potential is added by the long-range
+    /// correction.
+    pub(crate) fn dispersion_scaling(&self) -> (Array2<f64>, Array2<f64>) {
+        let n = self.lambda_r.len();
+        let cutoff = PETS_CUTOFF;
+        let (j1_lj, j2_lj) = mean_field_integrals(LJ_EXPONENT, cutoff);
+        let mut s1 = Array2::ones((n, n));
+        let mut s2 = Array2::ones((n, n));
+        for i in 0..n {
+            for j in 0..n {
+                let n_ij = self.lambda_r_ij(i, j);
+                if n_ij != LJ_EXPONENT {
+                    let (j1, j2) = mean_field_integrals(n_ij, cutoff);
+                    s1[(i, j)] = j1 / j1_lj;
+                    s2[(i, j)] = j2 / j2_lj;
+                }
+            }
+        }
+        (s1, s2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eos::{Pets, PetsOptions};
+    use crate::parameters::utils::{argon_krypton_parameters, argon_parameters, map_model_records};
+    use crate::PetsFunctional;
+    use approx::assert_relative_eq;
+    use feos_core::{Contributions, EquationOfState, State};
+    use feos_dft::solvation::PairPotential;
+    use ndarray::arr1;
+    use num_dual::Dual64;
+    use quantity::si::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn lennard_jones() {
+        assert_eq!(mie_prefactor(LJ_EXPONENT), 4.0);
+        assert_relative_eq!(
+            u_mie(2f64.powf(1.0 / 6.0), LJ_EXPONENT),
+            -1.0,
+            max_relative = 1e-14
+        );
+        for n in [8.0, 10.0, 16.0] {
+            // the minimum of the potential is -epsilon
+            let x_min = (n / 6.0).powf(1.0 / (n - 6.0));
+            assert_relative_eq!(u_mie(x_min, n), -1.0, max_relative = 1e-12);
+        }
+    }
+
+    #[test]
+    fn mean_field_integrals_numerical() {
+        // trapezoidal rule
+        for cutoff in [PETS_CUTOFF, 4.0] {
+            for n in [10.0, 12.0, 14.0] {
+                let points = 200000;
+                let dx = (cutoff - 1.0) / points as f64;
+                let (mut j1, mut j2) = (0.0, 0.0);
+                for i in 0..=points {
+                    let x = 1.0 + i as f64 * dx;
+                    let w = if i == 0 || i == points { 0.5 } else { 1.0 };
+                    let u = u_mie(x, n) - u_mie(cutoff, n);
+                    j1 += w * u * x * x * dx;
+                    j2 += w * u * u * x * x * dx;
+                }
+                let (j1_analytic, j2_analytic) = mean_field_integrals(n, cutoff);
+                assert_relative_eq!(j1_analytic, j1, max_relative = 1e-8);
+                assert_relative_eq!(j2_analytic, j2, max_relative = 1e-8);
+            }
+        }
+
+        // full potential
+        for n in [10.0, 12.0, 14.0] {
+            let (j1, j2) = mean_field_integrals(n, f64::INFINITY);
+            let (j1_c, j2_c) = mean_field_integrals(n, 1e4);
+            assert_relative_eq!(j1, j1_c, max_relative = 1e-8);
+            assert_relative_eq!(j2, j2_c, max_relative = 1e-8);
+        }
+    }
+
+    #[test]
+    fn barker_henderson() {
+        // trapezoidal rule of 1 - exp(-u / kT) from 0 to 1
+        for (n, t) in [(12.0, 0.8), (12.0, 5.0), (9.0, 1.2), (20.0, 1.5)] {
+            let points = 200000;
+            let dx = 1.0 / points as f64;
+            let d = (1..=points)
+                .map(|i| {
+                    let x = i as f64 * dx;
+                    let w = if i == points { 0.5 } else { 1.0 };
+                    w * (1.0 - (-u_mie(x, n) / t).exp()) * dx
+                })
+                .sum::<f64>()
+                + 0.5 * dx;
+            assert_relative_eq!(
+                barker_henderson_diameter(n, t, f64::INFINITY),
+                d,
+                max_relative = 1e-8
+            );
+        }
+
+        // truncated and shifted potentials
+        for (n, t, cutoff) in [(12.0, 1.0, PETS_CUTOFF), (12.0, 1.0, 4.0), (10.0, 1.5, 3.0)] {
+            let u_c = u_mie(cutoff, n);
+            let x_s = inverse_repulsion(u_c, n, 1.0);
+            let points = 200000;
+            let dx = x_s / points as f64;
+            let d = (1..=points)
+                .map(|i| {
+                    let x = i as f64 * dx;
+                    let w = if i == points { 0.5 } else { 1.0 };
+                    w * (1.0 - (-(u_mie(x, n) - u_c) / t).exp()) * dx
+                })
+                .sum::<f64>()
+                + 0.5 * dx;
+            assert!(x_s > 1.0);
+            assert_relative_eq!(
+                barker_henderson_diameter(n, t, cutoff),
+                d,
+                max_relative = 1e-8
+            );
+        }
+
+        // temperature derivative
+        for cutoff in [PETS_CUTOFF, f64::INFINITY] {
+            let (n, t, h) = (12.0, 1.2, 1e-5);
+            let d = barker_henderson_diameter(n, Dual64::from(t).derive(), cutoff);
+            assert_relative_eq!(
+                d.eps[0],
+                (barker_henderson_diameter(n, t + h, cutoff)
+                    - barker_henderson_diameter(n, t - h, cutoff))
+                    / (2.0 * h),
+                max_relative = 1e-6
+            );
+        }
+    }
+
+    #[test]
+    fn lennard_jones_limit() {
+        let t = 150.0 * KELVIN;
+        let v = 1e-4 * METER.powi(3);
+        let n = arr1(&[0.3, 0.7]) * MOL;
+        let lj = argon_krypton_parameters();
+        let mie = map_model_records(&lj, |_, r| r.with_repulsive_exponent(LJ_EXPONENT));
+        let s_lj = State::new_nvt(&Rc::new(Pets::new(lj)), t, v, &n).unwrap();
+        let s_mie = State::new_nvt(&Rc::new(Pets::new(mie)), t, v, &n).unwrap();
+        assert_eq!(
+            s_lj.pressure(Contributions::Total),
+            s_mie.pressure(Contributions::Total)
+        );
+    }
+
+    #[test]
+    fn bulk_consistency() {
+        let t = 150.0 * KELVIN;
+        let v = 1e-4 * METER.powi(3);
+        for (parameters, n) in [
+            (
+                map_model_records(&argon_parameters(), |_, r| r.with_repulsive_exponent(10.0)),
+                arr1(&[1.0]) * MOL,
+            ),
+            (
+                map_model_records(&argon_krypton_parameters(), |i, r| {
+                    r.with_repulsive_exponent([10.0, 14.0][i])
+                }),
+                arr1(&[0.3, 0.7]) * MOL,
+            ),
+        ] {
+            let classical = Rc::new(Pets::new(map_model_records(&parameters, |_, mut r| {
+                r.lambda_r = None;
+                r
+            })));
+            let eos = Rc::new(Pets::new(parameters.clone()));
+            let func = Rc::new(PetsFunctional::new(parameters));
+            let s_lj = State::new_nvt(&classical, t, v, &n).unwrap();
+            let s_eos = State::new_nvt(&eos, t, v, &n).unwrap();
+            let s_func = State::new_nvt(&func, t, v, &n).unwrap();
+            assert!(s_eos.pressure(Contributions::Total) != s_lj.pressure(Contributions::Total));
+            assert_relative_eq!(
+                s_eos.pressure(Contributions::Total),
+                s_func.pressure(Contributions::Total),
+                max_relative = 1e-10
+            );
+            assert_relative_eq!(
+                s_eos.chemical_potential(Contributions::ResidualNvt),
+                s_func.chemical_potential(Contributions::ResidualNvt),
+                max_relative = 1e-10
+            );
+        }
+    }
+
+    #[test]
+    fn second_virial_coefficient() {
+        // reference: trapezoidal rule of 2 pi (1 - exp(-u / kT)) x² of the Mie potential
+        // truncated and shifted at 2.5 sigma and of the full potential. Below T* = 1.5,
+        // PeTS deviates by up to 0.2 sigma³ already for the Lennard-Jones potential.
+        let (sigma, epsilon_k) = (3.405, 119.8);
+        let b2_exact = |n: f64, t: f64, cutoff: f64| {
+            let u_c = if cutoff.is_finite() {
+                u_mie(cutoff, n)
+            } else {
+                0.0
+            };
+            let x_max = cutoff.min(50.0);
+            let points = 200000;
+            let dx = x_max / points as f64;
+            (1..=points)
+                .map(|i| {
+                    let x = i as f64 * dx;
+                    let w = if i == points { 0.5 } else { 1.0 };
+                    w * (1.0 - (-(u_mie(x, n) - u_c) / t).exp()) * x * x * dx
+                })
+                .sum::<f64>()
+                * 2.0
+                * std::f64::consts::PI
+        };
+        for (tail_correction, cutoff) in [(false, PETS_CUTOFF), (true, f64::INFINITY)] {
+            let options = PetsOptions {
+                tail_correction,
+                ..Default::default()
+            };
+            for n in [10.0, 14.0, 20.0] {
+                let parameters =
+                    map_model_records(&argon_parameters(), |_, r| r.with_repulsive_exponent(n));
+                let eos = Rc::new(Pets::with_options(parameters, options).unwrap());
+                for t in [1.5, 2.0, 3.0, 5.0] {
+                    let b2 = eos
+                        .second_virial_coefficient(t * epsilon_k * KELVIN, None)
+                        .unwrap()
+                        / (NAV * (sigma * ANGSTROM).powi(3));
+                    assert_relative_eq!(
+                        b2.into_value().unwrap(),
+                        b2_exact(n, t, cutoff),
+                        epsilon = 0.1
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn pair_potential() {
+        let n = 10.0;
+        let func = PetsFunctional::new(map_model_records(&argon_parameters(), |_, r| {
+            r.with_repulsive_exponent(n)
+        }));
+        let sigma = 3.405;
+        let r_min = sigma * (n / 6.0).powf(1.0 / (n - 6.0));
+        let u = func.pair_potential(&arr1(&[sigma, r_min]));
+        let u_c = 119.8 * u_mie(PETS_CUTOFF, n);
+        assert_relative_eq!(u[(0, 0)], -u_c, max_relative = 1e-12);
+        assert_relative_eq!(u[(0, 1)], -119.8 - u_c, max_relative = 1e-12);
+    }
+}
@@ -1,36 +1,64 @@
-use crate::parameters::PetsParameters;
-use feos_core::joback::Joback;
+use crate::parameters::{PetsParameters, TransportProperty};
 use feos_core::parameter::Parameter;
 use feos_core::{
-    Contributions, EntropyScaling, EosError, EosResult, EosUnit, EquationOfState, HelmholtzEnergy,
-    IdealGasContribution, MolarWeight, State,
+    Contributions, EntropyScaling, EosResult, EosUnit, EquationOfState, HelmholtzEnergy,
+    IdealGasContribution, MolarWeight, State, StateHD,
 };
-use ndarray::Array1;
+use ndarray::{Array1, Array2};
+use num_dual::HyperDual64;
 use quantity::si::*;
 use std::f64::consts::{FRAC_PI_6, PI};
 use std::rc::Rc;
 
+pub(crate) mod association;
+pub(crate) mod cutoff;
 pub(crate) mod dispersion;
+mod feynman_hibbs;
+pub(crate) mod hard_chain;
 pub(crate) mod hard_sphere;
+pub(crate) mod ideal_gas;
+mod lennard_jones;
+pub(crate) mod mie;
+pub(crate) mod polar;
 mod qspr;
+use association::Association;
+use cutoff::CutoffCorrection;
 use dispersion::Dispersion;
+use hard_chain::HardChain;
 use hard_sphere::HardSphere;
-use qspr::QSPR;
+pub use hard_sphere::{HardSphereDiameter, HardSphereModel};
+use ideal_gas::IdealGasContributions;
+pub use ideal_gas::IdealGasModel;
+pub use lennard_jones::LennardJones;
+use polar::{Dipole, DipoleQuadrupole, Quadrupole};
 
-#[allow(clippy::upper_case_acronyms)]
-enum IdealGasContributions {
-    QSPR(QSPR),
-    Joback(Joback),
-}
-
-#[derive(Copy, Clone)]
+#[derive(Debug, Copy, Clone)]
 pub struct PetsOptions {
     pub max_eta: f64,
+    /// Apply the first-order Feynman-Hibbs correction to `sigma` and `epsilon_k`.
+    pub feynman_hibbs: bool,
+    /// Truncate the potential at 2.5 sigma without shifting and add the
+    /// mean-field long-range correction beyond the cutoff.
+    pub tail_correction: bool,
+    /// Hard-sphere mixture reference of the equation of state.
+    /// `PetsFunctional` always uses fundamental measure theory.
+    pub hard_sphere_model: HardSphereModel,
+    /// Model of the hard-sphere diameter.
+    pub hs_diameter: HardSphereDiameter,
+    /// Model of the ideal gas contribution.
+    pub ideal_gas: IdealGasModel,
 }
 
 impl Default for PetsOptions {
     fn default() -> Self {
-        Self { max_eta: 0.5 }
+        Self {
+            max_eta: 0.5,
+            feynman_hibbs: false,
+            tail_correction: false,
+            hard_sphere_model: HardSphereModel::BMCSL,
+            hs_diameter: HardSphereDiameter::Correlation,
+            ideal_gas: IdealGasModel::Automatic,
+        }
     }
 }
 
@@ -43,29 +71,62 @@ pub struct Pets {
 
 impl Pets {
     pub fn new(parameters: Rc<PetsParameters>) -> Self {
-        Self::with_options(parameters, PetsOptions::default())
+        // the default ideal gas model is available for all parameters
+        Self::with_options(parameters, PetsOptions::default()).unwrap()
     }
 
-    pub fn with_options(parameters: Rc<PetsParameters>, options: PetsOptions) -> Self {
-        let mut contributions: Vec<Box<dyn HelmholtzEnergy>> = Vec::with_capacity(2);
-        contributions.push(Box::new(HardSphere {
+    pub fn with_options(parameters: Rc<PetsParameters>, options: PetsOptions) -> EosResult<Self> {
+        let mut contributions: Vec<Box<dyn HelmholtzEnergy>> = vec![Box::new(HardSphere {
             parameters: parameters.clone(),
-        }));
+            options,
+        })];
+        if parameters.has_chains() {
+            contributions.push(Box::new(HardChain {
+                parameters: parameters.clone(),
+                options,
+            }));
+        }
         contributions.push(Box::new(Dispersion {
             parameters: parameters.clone(),
+            options,
         }));
+        if parameters.has_dipoles() {
+            contributions.push(Box::new(Dipole {
+                parameters: parameters.clone(),
+                options,
+            }));
+        }
+        if parameters.has_quadrupoles() {
+            contributions.push(Box::new(Quadrupole {
+                parameters: parameters.clone(),
+                options,
+            }));
+        }
+        if parameters.has_dipoles() && parameters.has_quadrupoles() {
+            contributions.push(Box::new(DipoleQuadrupole {
+                parameters: parameters.clone(),
+                options,
+            }));
+        }
+        if parameters.has_association() {
+            contributions.push(Box::new(Association {
+                parameters: parameters.clone(),
+                options,
+            }));
+        }
+        if options.tail_correction {
+            contributions.push(Box::new(CutoffCorrection {
+                parameters: parameters.clone(),
+                options,
+            }));
+        }
 
-        let joback_records = parameters.joback_records.clone();
-
-        Self {
-            parameters: parameters.clone(),
+        Ok(Self {
+            ideal_gas: IdealGasContributions::new(&parameters, options.ideal_gas)?,
+            parameters,
             options,
             contributions,
-            ideal_gas: joback_records.map_or(
-                IdealGasContributions::QSPR(QSPR { parameters }),
-                |joback_records| IdealGasContributions::Joback(Joback::new(joback_records)),
-            ),
-        }
+        })
     }
 }
 
@@ -79,11 +140,13 @@ impl EquationOfState for Pets {
             Rc::new(self.parameters.subset(component_list)),
             self.options,
         )
+        .expect("The subset contains the ideal gas records of the components.")
     }
 
     fn compute_max_density(&self, moles: &Array1<f64>) -> f64 {
         self.options.max_eta * moles.sum()
-            / (FRAC_PI_6 * self.parameters.sigma.mapv(|v| v.powi(3)) * moles).sum()
+            / (FRAC_PI_6 * &self.parameters.m * self.parameters.sigma.mapv(|v| v.powi(3)) * moles)
+                .sum()
     }
 
     fn residual(&self) -> &[Box<dyn HelmholtzEnergy>] {
@@ -91,10 +154,7 @@ impl EquationOfState for Pets {
     }
 
     fn ideal_gas(&self) -> &dyn IdealGasContribution {
-        match &self.ideal_gas {
-            IdealGasContributions::QSPR(qspr) => qspr,
-            IdealGasContributions::Joback(joback) => joback,
-        }
+        self.ideal_gas.as_dyn()
     }
 }
 
@@ -116,6 +176,96 @@ fn omega22(t: f64) -> f64 {
         - 6.435e-4 * t.powf(0.14874) * (18.0323 * t.powf(-0.76830) - 7.27371).sin()
 }
 
+impl Pets {
+    /// Chapman-Enskog diffusion coefficients of all pairs of components
+    /// using the cross parameters `sigma_ij` and `epsilon_k_ij`.
+    fn binary_diffusion_reference(
+        &self,
+        temperature: SINumber,
+        density: SINumber,
+    ) -> EosResult<Array2<SINumber>> {
+        let p = &self.parameters;
+        let t = temperature.to_reduced(KELVIN)?;
+        let epsilon_k_ij = p.epsilon_k_ij(t);
+        let mw = &p.molarweight;
+        let n = self.components();
+        Ok(Array2::from_shape_fn((n, n), |(i, j)| {
+            let reduced_mass = mw[i] * mw[j] / (mw[i] + mw[j]) * GRAM / MOL;
+            3.0 / 8.0
+                / (p.sigma_ij[(i, j)] * ANGSTROM).powi(2)
+                / omega11(t / epsilon_k_ij[(i, j)])
+                / (density * NAV)
+                * (temperature * RGAS / (2.0 * PI * reduced_mass))
+                    .sqrt()
+                    .unwrap()
+        }))
+    }
+
+    /// Chapman-Enskog reference for the self-diffusion coefficients
+    /// of all components in a mixture, combined using Blanc's law.
+    fn component_diffusion_reference(
+        &self,
+        temperature: SINumber,
+        volume: SINumber,
+        moles: &SIArray1,
+    ) -> EosResult<SIArray1> {
+        let x = moles.to_reduced(moles.sum())?;
+        let d = self.binary_diffusion_reference(temperature, moles.sum() / volume)?;
+        Ok(SIArray1::from_vec(
+            (0..self.components())
+                .map(|i| {
+                    1.0 / (0..self.components()).fold(0.0 * SECOND / METER.powi(2), |acc, j| {
+                        acc + x[j] / d[(i, j)]
+                    })
+                })
+                .collect(),
+        ))
+    }
+
+    /// Reduced residual molar entropy and molar isochoric heat capacity,
+    /// evaluated directly from the Helmholtz energy without creating a [State].
+    fn entropy_heat_capacity(
+        &self,
+        temperature: SINumber,
+        volume: SINumber,
+        moles: &SIArray1,
+    ) -> EosResult<(f64, SINumber)> {
+        let t = temperature.to_reduced(KELVIN)?;
+        let v = volume.to_reduced(SIUnit::reference_volume())?;
+        let n = moles.to_reduced(SIUnit::reference_moles())?;
+        let state = StateHD::new(
+            HyperDual64::new_scalar(t, 1.0, 1.0, 0.0),
+            HyperDual64::from(v),
+            n.mapv(HyperDual64::from),
+        );
+        let a_res = self.evaluate_residual(&state);
+        let a = a_res + self.ideal_gas().evaluate(&state);
+
+        // With A = kTa: S/k = -(a + T da/dT) and C_v/k = -T (2 da/dT + T d2a/dT2)
+        let n = n.sum();
+        let s_res = -(a_res.re + t * a_res.eps1[0]) / n;
+        let c_v = -t * (2.0 * a.eps1[0] + t * a.eps1eps2[(0, 0)]) / n;
+        Ok((s_res, c_v * RGAS))
+    }
+
+    /// Self-diffusion coefficients of all components in a mixture.
+    ///
+    /// The Chapman-Enskog reference of each component is calculated from
+    /// the binary reference values using Blanc's law and scaled with the
+    /// diffusion correlation of the mixture. For a component that is not
+    /// present in the mixture, the result is its diffusion coefficient at
+    /// infinite dilution.
+    pub fn self_diffusion_coefficients(&self, state: &State<SIUnit, Self>) -> EosResult<SIArray1> {
+        let s_res = state
+            .molar_entropy(Contributions::ResidualNvt)
+            .to_reduced(SIUnit::reference_molar_entropy())?;
+        Ok(
+            self.component_diffusion_reference(state.temperature, state.volume, &state.moles)?
+                * self.diffusion_correlation(s_res, &state.molefracs)?.exp(),
+        )
+    }
+}
+
 impl EntropyScaling<SIUnit> for Pets {
     fn viscosity_reference(
         &self,
@@ -156,11 +306,10 @@ impl EntropyScaling<SIUnit> for Pets {
     }
 
     fn viscosity_correlation(&self, s_res: f64, x: &Array1<f64>) -> EosResult<f64> {
-        let coefficients = self
-            .parameters
-            .viscosity
-            .as_ref()
-            .expect("Missing viscosity coefficients.");
+        let coefficients = self.parameters.viscosity.as_ref().ok_or_else(|| {
+            self.parameters
+                .missing_coefficients(TransportProperty::Viscosity)
+        })?;
         let a: f64 = (&coefficients.row(0) * x).sum();
         let b: f64 = (&coefficients.row(1) * x).sum();
         let c: f64 = (&coefficients.row(2) * x).sum();
@@ -174,34 +323,16 @@ impl EntropyScaling<SIUnit> for Pets {
         volume: SINumber,
         moles: &SIArray1,
     ) -> EosResult<SINumber> {
-        if self.components() != 1 {
-            return Err(EosError::IncompatibleComponents(self.components(), 1));
-        }
-        let p = &self.parameters;
-        let density = moles.sum() / volume;
-        let res: Array1<SINumber> = (0..self.components())
-            .map(|i| {
-                let tr = (temperature / p.epsilon_k[i] / KELVIN)
-                    .into_value()
-                    .unwrap();
-                3.0 / 8.0 / (p.sigma[i] * ANGSTROM).powi(2) / omega11(tr) / (density * NAV)
-                    * (temperature * RGAS / PI / (p.molarweight[i] * GRAM / MOL))
-                        .sqrt()
-                        .unwrap()
-            })
-            .collect();
-        Ok(res[0])
+        let x = moles.to_reduced(moles.sum())?;
+        let d = self.component_diffusion_reference(temperature, volume, moles)?;
+        Ok((d * &x).sum())
     }
 
     fn diffusion_correlation(&self, s_res: f64, x: &Array1<f64>) -> EosResult<f64> {
-        if self.components() != 1 {
-            return Err(EosError::IncompatibleComponents(self.components(), 1));
-        }
-        let coefficients = self
-            .parameters
-            .diffusion
-            .as_ref()
-            .expect("Missing diffusion coefficients.");
+        let coefficients = self.parameters.diffusion.as_ref().ok_or_else(|| {
+            self.parameters
+                .missing_coefficients(TransportProperty::Diffusion)
+        })?;
         let a: f64 = (&coefficients.row(0) * x).sum();
         let b: f64 = (&coefficients.row(1) * x).sum();
         let c: f64 = (&coefficients.row(2) * x).sum();
@@ -253,61 +384,52 @@ impl EntropyScaling<SIUnit> for Pets {
     // }
 
     // Equation 11 of DOI: 10.1021/acs.iecr.9b03998
+    // For mixtures, the Chapman-Enskog contributions of the components are
+    // combined using the Wassiljewa equation with the Mason-Saxena modification.
     fn thermal_conductivity_reference(
         &self,
         temperature: SINumber,
         volume: SINumber,
         moles: &SIArray1,
     ) -> EosResult<SINumber> {
-        if self.components() != 1 {
-            return Err(EosError::IncompatibleComponents(self.components(), 1));
-        }
+        let x = moles.to_reduced(moles.sum())?;
         let p = &self.parameters;
-        let state = State::new_nvt(
-            &Rc::new(Self::new(self.parameters.clone())),
-            temperature,
-            volume,
-            moles,
-        )?;
-        let res: Array1<SINumber> = (0..self.components())
+        let mw = &p.molarweight;
+        let t = temperature.to_reduced(KELVIN)?;
+        let ce: Array1<f64> = (0..self.components())
             .map(|i| {
-                let tr = (temperature / p.epsilon_k[i] / KELVIN)
-                    .into_value()
-                    .unwrap();
-                let ce = 83.235
-                    * f64::powf(10.0, -1.5)
-                    * ((temperature / KELVIN).into_value().unwrap() / p.molarweight[0]).sqrt()
-                    / (p.sigma[0] * p.sigma[0])
-                    / omega22(tr);
-                ce * WATT / METER / KELVIN
-                    + state.density
-                        * self
-                            .diffusion_reference(temperature, volume, moles)
-                            .unwrap()
-                        * self
-                            .diffusion_correlation(
-                                state
-                                    .molar_entropy(Contributions::ResidualNvt)
-                                    .to_reduced(SIUnit::reference_molar_entropy())
-                                    .unwrap(),
-                                &state.molefracs,
-                            )
-                            .unwrap()
-                        * (state.c_v(Contributions::Total) - 1.5 * RGAS)
+                83.235 * f64::powf(10.0, -1.5) * (t / mw[i]).sqrt()
+                    / (p.sigma[i] * p.sigma[i])
+                    / omega22(t / p.epsilon_k[i])
             })
             .collect();
-        Ok(res[0])
+        let mut ce_mix = 0.0;
+        for i in 0..self.components() {
+            let denom: f64 = (0..self.components())
+                .map(|j| {
+                    x[j] * (1.0 + (ce[i] / ce[j]).sqrt() * (mw[i] / mw[j]).powf(1.0 / 4.0)).powi(2)
+                        / (8.0 * (1.0 + mw[i] / mw[j])).sqrt()
+                })
+                .sum();
+            ce_mix += ce[i] * x[i] / denom
+        }
+        let (s_res, c_v) = self.entropy_heat_capacity(temperature, volume, moles)?;
+        Ok(ce_mix * WATT / METER / KELVIN
+            + moles.sum() / volume
+                * self.diffusion_reference(temperature, volume, moles)?
+                * self.diffusion_correlation(s_res, &x)?
+                * (c_v - 1.5 * RGAS))
     }
 
     fn thermal_conductivity_correlation(&self, s_res: f64, x: &Array1<f64>) -> EosResult<f64> {
-        if self.components() != 1 {
-            return Err(EosError::IncompatibleComponents(self.components(), 1));
-        }
         let coefficients = self
             .parameters
             .thermal_conductivity
             .as_ref()
-            .expect("Missing thermal conductivity coefficients");
+            .ok_or_else(|| {
+                self.parameters
+                    .missing_coefficients(TransportProperty::ThermalConductivity)
+            })?;
         let a: f64 = (&coefficients.row(0) * x).sum();
         let b: f64 = (&coefficients.row(1) * x).sum();
         let c: f64 = (&coefficients.row(2) * x).sum();
@@ -320,12 +442,14 @@ impl EntropyScaling<SIUnit> for Pets {
 mod tests {
     use super::*;
     use crate::parameters::utils::{
-        argon_krypton_parameters, argon_parameters, krypton_parameters,
+        argon_krypton_parameters, argon_parameters, krypton_parameters, map_model_records,
     };
+    use crate::parameters::PetsRecord;
     use approx::assert_relative_eq;
     use feos_core::{Contributions, DensityInitialization, PhaseEquilibrium, State};
     use ndarray::arr1;
     use quantity::si::{BAR, KELVIN, METER, PASCAL, RGAS};
+    use std::time::Instant;
 
     #[test]
     fn ideal_gas_pressure() {
@@ -413,4 +537,428 @@ mod tests {
             epsilon = 1e-12
         )
     }
+
+    #[test]
+    fn missing_entropy_scaling_coefficients() {
+        let p = map_model_records(&argon_krypton_parameters(), |i, r| {
+            if i == 1 {
+                PetsRecord {
+                    viscosity: None,
+                    diffusion: None,
+                    thermal_conductivity: None,
+                    ..r
+                }
+            } else {
+                r
+            }
+        });
+        let e = Rc::new(Pets::new(p));
+        let s = State::new_nvt(
+            &e,
+            300.0 * KELVIN,
+            1e-3 * METER.powi(3),
+            &(arr1(&[1.0, 1.0]) * MOL),
+        )
+        .unwrap();
+        let viscosity = s.viscosity().unwrap_err().to_string();
+        assert!(viscosity.contains("viscosity") && viscosity.contains("krypton"));
+        let diffusion = s.diffusion().unwrap_err().to_string();
+        assert!(diffusion.contains("diffusion") && diffusion.contains("krypton"));
+        let thermal_conductivity = s.thermal_conductivity().unwrap_err().to_string();
+        assert!(thermal_conductivity.contains("krypton"));
+
+        // pure components without coefficients
+        let e = Rc::new(Pets::new(krypton_parameters()));
+        let s = State::new_nvt(
+            &e,
+            300.0 * KELVIN,
+            1e-3 * METER.powi(3),
+            &(arr1(&[1.0]) * MOL),
+        )
+        .unwrap();
+        assert!(s.viscosity().is_err());
+        assert!(s.diffusion().is_err());
+        assert!(s.thermal_conductivity().is_err());
+    }
+
+    #[test]
+    fn diffusion_mix_single() {
+        let e1 = Rc::new(Pets::new(argon_parameters()));
+        let e2 = Rc::new(Pets::new(krypton_parameters()));
+        let e12 = Rc::new(Pets::new(argon_krypton_parameters()));
+        let t = 150.0 * KELVIN;
+        let v = 1e-4 * METER.powi(3);
+        let m1 = arr1(&[2.0]) * MOL;
+        let m1m = arr1(&[2.0, 0.0]) * MOL;
+        let m2m = arr1(&[0.0, 2.0]) * MOL;
+        let s1 = State::new_nvt(&e1, t, v, &m1).unwrap();
+        let s2 = State::new_nvt(&e2, t, v, &m1).unwrap();
+        let s1m = State::new_nvt(&e12, t, v, &m1m).unwrap();
+        let s2m = State::new_nvt(&e12, t, v, &m2m).unwrap();
+        assert_relative_eq!(
+            s1.diffusion().unwrap(),
+            s1m.diffusion().unwrap(),
+            max_relative = 1e-12
+        );
+        assert_relative_eq!(
+            s2.diffusion_reference().unwrap(),
+            s2m.diffusion_reference().unwrap(),
+            max_relative = 1e-12
+        );
+        assert_relative_eq!(
+            e12.self_diffusion_coefficients(&s1m).unwrap().get(0),
+            s1.diffusion().unwrap(),
+            max_relative = 1e-12
+        );
+        assert_relative_eq!(
+            e12.self_diffusion_coefficients(&s2m).unwrap().get(1),
+            s2.diffusion_reference().unwrap(),
+            max_relative = 1e-12
+        );
+    }
+
+    #[test]
+    fn diffusion_infinite_dilution() {
+        let e = Rc::new(Pets::new(argon_krypton_parameters()));
+        let t = 150.0 * KELVIN;
+        let v = 1e-4 * METER.powi(3);
+        let n = arr1(&[0.0, 2.0]) * MOL;
+        let s = State::new_nvt(&e, t, v, &n).unwrap();
+
+        // Chapman-Enskog diffusion coefficient of argon in krypton
+        let sigma = 0.5 * (3.4050 + 3.6300) * ANGSTROM;
+        let epsilon_k = (119.8f64 * 163.10).sqrt();
+        let reduced_mass = 39.948 * 83.798 / (39.948 + 83.798) * GRAM / MOL;
+        let d12 = 3.0 / 8.0 / sigma.powi(2) / omega11(150.0 / epsilon_k) / (n.sum() / v * NAV)
+            * (t * RGAS / (2.0 * PI * reduced_mass)).sqrt().unwrap();
+        assert_relative_eq!(
+            e.self_diffusion_coefficients(&s).unwrap().get(0),
+            d12,
+            max_relative = 1e-12
+        );
+    }
+
+    #[test]
+    fn diffusion_mix() {
+        let coefficients = [
+            [0.1, -0.3, 0.05, 0.002, 1e-5],
+            [-0.2, -0.1, 0.2, -0.001, 0.0],
+        ];
+        let p = map_model_records(&argon_krypton_parameters(), |i, r| PetsRecord {
+            diffusion: Some(coefficients[i]),
+            ..r
+        });
+        let eos = Rc::new(Pets::new(p));
+        let t = 150.0 * KELVIN;
+        let v = 1e-4 * METER.powi(3);
+        let n = arr1(&[0.5, 1.5]) * MOL;
+        let s = State::new_nvt(&eos, t, v, &n).unwrap();
+        let s_res = s
+            .molar_entropy(Contributions::ResidualNvt)
+            .to_reduced(SIUnit::reference_molar_entropy())
+            .unwrap();
+
+        // Chapman-Enskog diffusion coefficients of all pairs
+        let sigma = [3.4050, 3.6300];
+        let epsilon_k = [119.8f64, 163.10];
+        let mw = [39.948, 83.798];
+        let d_ij = |i: usize, j: usize| {
+            let sigma = 0.5 * (sigma[i] + sigma[j]) * ANGSTROM;
+            let epsilon_k = (epsilon_k[i] * epsilon_k[j]).sqrt();
+            let reduced_mass = mw[i] * mw[j] / (mw[i] + mw[j]) * GRAM / MOL;
+            3.0 / 8.0 / sigma.powi(2) / omega11(150.0 / epsilon_k) / (n.sum() / v * NAV)
+                * (t * RGAS / (2.0 * PI * reduced_mass)).sqrt().unwrap()
+        };
+
+        // Blanc's law and the coefficients mixed with x = (0.25, 0.75)
+        let x = [0.25, 0.75];
+        let [a, b, c, d, e] = [-0.125, -0.15, 0.1625, -2.5e-4, 2.5e-6];
+        let correlation = (a + b * s_res
+            - c * (1.0 - s_res.exp()) * s_res.powi(2)
+            - d * s_res.powi(4)
+            - e * s_res.powi(8))
+        .exp();
+        let d_self: Vec<_> = (0..2)
+            .map(|i| correlation / (x[0] / d_ij(i, 0) + x[1] / d_ij(i, 1)))
+            .collect();
+
+        let d_calc = eos.self_diffusion_coefficients(&s).unwrap();
+        for (i, &d) in d_self.iter().enumerate() {
+            assert_relative_eq!(d_calc.get(i), d, max_relative = 1e-12);
+        }
+        assert!((d_self[0] / d_self[1]).into_value().unwrap() > 1.1);
+        assert_relative_eq!(
+            s.diffusion().unwrap(),
+            x[0] * d_self[0] + x[1] * d_self[1],
+            max_relative = 1e-12
+        );
+    }
+
+    #[test]
+    fn thermal_conductivity_mix() {
+        let e1 = Rc::new(Pets::new(argon_parameters()));
+        let e12 = Rc::new(Pets::new(argon_krypton_parameters()));
+        let e21 = Rc::new(e12.subset(&[1, 0]));
+        let t = 150.0 * KELVIN;
+        let v = 1e-4 * METER.powi(3);
+        let s1 = State::new_nvt(&e1, t, v, &(arr1(&[2.0]) * MOL)).unwrap();
+        let s1m = State::new_nvt(&e12, t, v, &(arr1(&[2.0, 0.0]) * MOL)).unwrap();
+        assert_relative_eq!(
+            s1.thermal_conductivity().unwrap(),
+            s1m.thermal_conductivity().unwrap(),
+            max_relative = 1e-12
+        );
+
+        // the result does not depend on the order of the components
+        let s12 = State::new_nvt(&e12, t, v, &(arr1(&[0.5, 1.5]) * MOL)).unwrap();
+        let s21 = State::new_nvt(&e21, t, v, &(arr1(&[1.5, 0.5]) * MOL)).unwrap();
+        assert_relative_eq!(
+            s12.thermal_conductivity().unwrap(),
+            s21.thermal_conductivity().unwrap(),
+            max_relative = 1e-12
+        );
+    }
+
+    #[test]
+    fn thermal_conductivity_mix_coefficients() {
+        let coefficients = [[0.1, -0.5, 0.3, 0.05], [-0.3, -0.2, 0.1, -0.02]];
+        let p = map_model_records(&argon_krypton_parameters(), |i, r| PetsRecord {
+            thermal_conductivity: Some(coefficients[i]),
+            diffusion: Some([0.2, -0.1, 0.05, 0.0, 0.0]),
+            ..r
+        });
+        let eos = Rc::new(Pets::new(p));
+        let t = 150.0 * KELVIN;
+        let v = 1e-4 * METER.powi(3);
+        let n = arr1(&[0.5, 1.5]) * MOL;
+        let s = State::new_nvt(&eos, t, v, &n).unwrap();
+        let s_res = s
+            .molar_entropy(Contributions::ResidualNvt)
+            .to_reduced(SIUnit::reference_molar_entropy())
+            .unwrap();
+
+        // Chapman-Enskog thermal conductivities in W/(m K)
+        let sigma = [3.4050f64, 3.6300];
+        let epsilon_k = [119.8, 163.10];
+        let mw = [39.948f64, 83.798];
+        let lambda: Vec<_> = (0..2)
+            .map(|i| {
+                83.235 * 10f64.powf(-1.5) * (150.0 / mw[i]).sqrt()
+                    / sigma[i].powi(2)
+                    / omega22(150.0 / epsilon_k[i])
+            })
+            .collect();
+
+        // Wassiljewa equation with the Mason-Saxena modification
+        let x = [0.25, 0.75];
+        let phi = |i: usize, j: usize| {
+            (1.0 + (lambda[i] / lambda[j]).sqrt() * (mw[i] / mw[j]).powf(0.25)).powi(2)
+                / (8.0 * (1.0 + mw[i] / mw[j])).sqrt()
+        };
+        let lambda_ce: f64 = (0..2)
+            .map(|i| x[i] * lambda[i] / (x[0] * phi(i, 0) + x[1] * phi(i, 1)))
+            .sum();
+        let d_ref = s.diffusion_reference().unwrap();
+        let d_correlation = (s.diffusion().unwrap() / d_ref).into_value().unwrap().ln();
+        let reference = lambda_ce * WATT / METER / KELVIN
+            + n.sum() / v * d_ref * d_correlation * (s.c_v(Contributions::Total) - 1.5 * RGAS);
+        assert_relative_eq!(
+            s.thermal_conductivity_reference().unwrap(),
+            reference,
+            max_relative = 1e-10
+        );
+
+        // coefficients mixed with x = (0.25, 0.75)
+        let [a, b, c, d] = [-0.2, -0.275, 0.15, -0.0025];
+        let correlation = a + b * s_res + c * (1.0 - s_res.exp()) + d * s_res.powi(2);
+        assert_relative_eq!(
+            s.thermal_conductivity().unwrap(),
+            reference * correlation.exp(),
+            max_relative = 1e-10
+        );
+    }
+
+    /// Residual entropy and heat capacity as calculated previously
+    /// in `thermal_conductivity_reference`.
+    fn entropy_heat_capacity_state(
+        eos: &Pets,
+        temperature: SINumber,
+        volume: SINumber,
+        moles: &SIArray1,
+    ) -> (f64, SINumber) {
+        let state = State::new_nvt(
+            &Rc::new(Pets::new(eos.parameters.clone())),
+            temperature,
+            volume,
+            moles,
+        )
+        .unwrap();
+        let s_res = state
+            .molar_entropy(Contributions::ResidualNvt)
+            .to_reduced(SIUnit::reference_molar_entropy())
+            .unwrap();
+        (s_res, state.c_v(Contributions::Total))
+    }
+
+    #[test]
+    fn entropy_heat_capacity() {
+        let e = Pets::new(argon_krypton_parameters());
+        let v = 1e-4 * METER.powi(3);
+        let n = arr1(&[0.5, 1.5]) * MOL;
+        for t in [100.0 * KELVIN, 150.0 * KELVIN, 300.0 * KELVIN] {
+            let (s_res, c_v) = e.entropy_heat_capacity(t, v, &n).unwrap();
+            let (s_res_state, c_v_state) = entropy_heat_capacity_state(&e, t, v, &n);
+            assert_relative_eq!(s_res, s_res_state, max_relative = 1e-12);
+            assert_relative_eq!(c_v, c_v_state, max_relative = 1e-12);
+        }
+    }
+
+    /// Reference thermal conductivity of a pure component as calculated
+    /// previously in `thermal_conductivity_reference`.
+    fn thermal_conductivity_reference_state(
+        eos: &Pets,
+        temperature: SINumber,
+        volume: SINumber,
+        moles: &SIArray1,
+    ) -> SINumber {
+        let p = &eos.parameters;
+        let state =
+            State::new_nvt(&Rc::new(Pets::new(p.clone())), temperature, volume, moles).unwrap();
+        let tr = (temperature / p.epsilon_k[0] / KELVIN)
+            .into_value()
+            .unwrap();
+        let ce = 83.235
+            * f64::powf(10.0, -1.5)
+            * ((temperature / KELVIN).into_value().unwrap() / p.molarweight[0]).sqrt()
+            / (p.sigma[0] * p.sigma[0])
+            / omega22(tr);
+        ce * WATT / METER / KELVIN
+            + state.density
+                * eos.diffusion_reference(temperature, volume, moles).unwrap()
+                * eos
+                    .diffusion_correlation(
+                        state
+                            .molar_entropy(Contributions::ResidualNvt)
+                            .to_reduced(SIUnit::reference_molar_entropy())
+                            .unwrap(),
+                        &state.molefracs,
+                    )
+                    .unwrap()
+                * (state.c_v(Contributions::Total) - 1.5 * RGAS)
+    }
+
+    #[test]
+    fn thermal_conductivity_reference_pure() {
+        let p = map_model_records(&argon_parameters(), |_, r| PetsRecord {
+            diffusion: Some([0.2, -0.1, 0.05, 0.0, 0.0]),
+            ..r
+        });
+        let e = Pets::new(p);
+        let n = arr1(&[1.5]) * MOL;
+        for t in [100.0 * KELVIN, 150.0 * KELVIN, 300.0 * KELVIN] {
+            for v in [1e-4 * METER.powi(3), 1e-2 * METER.powi(3)] {
+                assert_relative_eq!(
+                    e.thermal_conductivity_reference(t, v, &n).unwrap(),
+                    thermal_conductivity_reference_state(&e, t, v, &n),
+                    max_relative = 1e-12
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[ignore]
+    fn entropy_heat_capacity_benchmark() {
+        let e = Pets::new(argon_krypton_parameters());
+        let v = 1e-4 * METER.powi(3);
+        let n = arr1(&[0.5, 1.5]) * MOL;
+        let temperatures: Vec<_> = (0..1000)
+            .map(|i| (100.0 + 0.2 * i as f64) * KELVIN)
+            .collect();
+
+        let start = Instant::now();
+        for &t in &temperatures {
+            e.entropy_heat_capacity(t, v, &n).unwrap();
+        }
+        let direct = start.elapsed();
+
+        let start = Instant::now();
+        for &t in &temperatures {
+            entropy_heat_capacity_state(&e, t, v, &n);
+        }
+        let state = start.elapsed();
+
+        assert!(
+            direct < state,
+            "direct evaluation: {:?}, via State: {:?}",
+            direct,
+            state
+        );
+    }
+
+    #[test]
+    fn cutoff_critical_temperature() {
+        let critical_temperature = |tail_correction| {
+            let options = PetsOptions {
+                tail_correction,
+                ..Default::default()
+            };
+            let e = Rc::new(Pets::with_options(argon_parameters(), options).unwrap());
+            let cp = State::critical_point(&e, None, None, Default::default()).unwrap();
+            cp.temperature.to_reduced(119.8 * KELVIN).unwrap()
+        };
+        let tc = critical_temperature(false);
+        let tc_full = critical_temperature(true);
+        assert!(tc < tc_full);
+        // full Lennard-Jones fluid (Potoff and Panagiotopoulos, 1998)
+        assert_relative_eq!(tc_full, 1.312, max_relative = 1e-2);
+    }
+
+    #[test]
+    fn tail_correction() {
+        let t = 120.0 * KELVIN;
+        let v = 1e-4 * METER.powi(3);
+        let n = arr1(&[1.0]) * MOL;
+        let pressure = |tail_correction| {
+            let options = PetsOptions {
+                tail_correction,
+                ..Default::default()
+            };
+            let e = Rc::new(Pets::with_options(argon_parameters(), options).unwrap());
+            State::new_nvt(&e, t, v, &n)
+                .unwrap()
+                .pressure(Contributions::Total)
+        };
+        // the attraction beyond the cutoff lowers the pressure
+        assert!(pressure(false) > pressure(true));
+    }
+
+    #[test]
+    fn hard_sphere_model() {
+        let t = 120.0 * KELVIN;
+        let v = 1e-4 * METER.powi(3);
+        let pressure = |parameters, n, hard_sphere_model| {
+            let options = PetsOptions {
+                hard_sphere_model,
+                ..Default::default()
+            };
+            let e = Rc::new(Pets::with_options(parameters, options).unwrap());
+            State::new_nvt(&e, t, v, &n)
+                .unwrap()
+                .pressure(Contributions::Total)
+        };
+        for (parameters, n, pure) in [
+            (argon_parameters(), arr1(&[1.0]) * MOL, true),
+            (argon_krypton_parameters(), arr1(&[1.0, 0.0]) * MOL, true),
+            (argon_krypton_parameters(), arr1(&[0.5, 0.5]) * MOL, false),
+        ] {
+            let bmcsl = pressure(parameters.clone(), n.clone(), HardSphereModel::BMCSL);
+            let syh = pressure(parameters, n, HardSphereModel::SYH);
+            if pure {
+                assert_relative_eq!(bmcsl, syh, max_relative = 1e-12);
+            } else {
+                assert!(bmcsl != syh);
+            }
+        }
+    }
 }
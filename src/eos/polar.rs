@@ -0,0 +1,813 @@
+use super::cutoff::shifted_cutoff;
+use super::mie::{u_mie, GAUSS_LEGENDRE, LJ_EXPONENT};
+use super::PetsOptions;
+use crate::parameters::PetsParameters;
+use feos_core::{HelmholtzEnergyDual, StateHD};
+use ndarray::*;
+use num_dual::DualNum;
+use std::f64::consts::{FRAC_PI_6, PI};
+use std::fmt;
+use std::rc::Rc;
+
+/// Conversion of squared dipole moments from Debye² to K Å³
+/// (and of squared quadrupole moments from (Debye Å)² to K Å⁵).
+const DEBYE2: f64 = 1e4 / 1.380649;
+
+// Coefficients of the dipole term of Gross and Vrabec (2006).
+const AD: [[f64; 3]; 5] = [
+    [0.3043504, 0.9534641, -1.1610080],
+    [-0.1358588, -1.8396383, 4.5258607],
+    [1.4493329, 2.0131180, 0.9751222],
+    [0.3556977, -7.3724958, -12.281038],
+    [-2.0653308, 8.2374135, 5.9397575],
+];
+const BD: [[f64; 3]; 5] = [
+    [0.2187939, -0.5873164, 3.4869576],
+    [-1.1896431, 1.2489132, -14.915974],
+    [1.1626889, -0.5085280, 15.372022],
+    [0.0, 0.0, 0.0],
+    [0.0, 0.0, 0.0],
+];
+const CD: [[f64; 3]; 4] = [
+    [-0.0646774, -0.9520876, -0.6260979],
+    [0.1975882, 2.9924258, 1.2924686],
+    [-0.8087562, -2.3802636, 1.6542783],
+    [0.6902849, -0.2701261, -3.4396744],
+];
+
+// Coefficients of the quadrupole term of Gross (2005).
+const AQ: [[f64; 3]; 5] = [
+    [1.2378308, 1.2854109, 1.7942954],
+    [2.4355031, -11.465615, 0.7695103],
+    [1.6330905, 22.086893, 7.2647923],
+    [-1.6118152, 7.4691383, 94.486699],
+    [6.9771185, -17.197772, -77.148458],
+];
+const BQ: [[f64; 3]; 5] = [
+    [0.4542718, -0.8137340, 6.8682675],
+    [-4.5016264, 10.064030, -5.1732238],
+    [3.5858868, -10.876631, -17.240207],
+    [0.0, 0.0, 0.0],
+    [0.0, 0.0, 0.0],
+];
+const CQ: [[f64; 3]; 4] = [
+    [-0.5000437, 2.0002094, 3.1358271],
+    [6.5318692, -6.7838658, 7.2475888],
+    [-16.014780, 20.383246, 3.0759478],
+    [14.425970, -10.895984, 0.0],
+];
+
+// Coefficients of the dipole-quadrupole term of Vrabec and Gross (2008).
+const ADQ: [[f64; 3]; 4] = [
+    [0.697094963, -0.673459279, 0.670340770],
+    [-0.633554144, -1.425899106, -4.338471826],
+    [2.945509028, 4.19441392, 7.234168360],
+    [-1.467027314, 1.032636420, -5.115486700],
+];
+const BDQ: [[f64; 3]; 4] = [
+    [-0.484038322, 0.67651011, -1.167560146],
+    [1.970405465, -3.013867512, 2.13488432],
+    [-2.118572671, 0.46742656, 0.0],
+    [0.0, 0.0, 0.0],
+];
+const CDQ: [[f64; 3]; 3] = [
+    [0.795009692, -2.099579397, 0.0],
+    [3.386863396, -5.941376392, 0.0],
+    [0.475106328, -0.178820384, 0.0],
+];
+
+/// Coefficient of the power series in the packing fraction for
+/// the (effective) segment number `m`.
+fn coefficient(c: &[f64; 3], m: f64) -> f64 {
+    let m = m.min(2.0);
+    c[0] + (m - 1.0) / m * c[1] + (m - 1.0) / m * (m - 2.0) / m * c[2]
+}
+
+/// Pair integral of a multipole term.
+fn pair_integral<D: DualNum<f64>>(
+    a: &[[f64; 3]],
+    b: &[[f64; 3]],
+    eta: D,
+    m: f64,
+    epsilon_t: D,
+) -> D {
+    let mut j2 = D::zero();
+    let mut eta_n = D::one();
+    for (a, b) in a.iter().zip(b.iter()) {
+        j2 += eta_n * (epsilon_t * coefficient(b, m) + coefficient(a, m));
+        eta_n *= eta;
+    }
+    j2
+}
+
+/// Triplet integral of a multipole term.
+fn triplet_integral<D: DualNum<f64>>(c: &[[f64; 3]], eta: D, m: f64) -> D {
+    let mut j3 = D::zero();
+    let mut eta_n = D::one();
+    for c in c.iter() {
+        j3 += eta_n * coefficient(c, m);
+        eta_n *= eta;
+    }
+    j3
+}
+
+/// Difference of the zero-density pair integrals `∫ x^-k exp(-u(x) / t) dx`
+/// of the Lennard-Jones potential truncated and shifted at `cutoff` and of
+/// the full Lennard-Jones potential at the reduced temperature `t`.
+///
+/// The coefficients of the polar terms were adjusted to the full potential.
+/// Adding this difference to the zero-density term of the pair integrals
+/// reproduces the exact second virial coefficient of the truncated and
+/// shifted potential; the difference vanishes for an infinite `cutoff`.
+fn truncation_correction<D: DualNum<f64>>(k: i32, t: D, cutoff: f64) -> D {
+    if cutoff.is_infinite() {
+        return D::zero();
+    }
+    let u_c = u_mie(cutoff, LJ_EXPONENT);
+    let t_inv = t.recip();
+    let gauss = |f: &dyn Fn(f64) -> D, a: f64, b: f64| {
+        let (h, m) = (0.5 * (b - a), 0.5 * (a + b));
+        GAUSS_LEGENDRE.iter().fold(D::zero(), |acc, &[xi, w]| {
+            acc + (f(m - h * xi) + f(m + h * xi)) * (w * h)
+        })
+    };
+
+    // below x0, the Boltzmann factors are negligible (u / kT > 50)
+    let x0 = (4.0 / (50.0 * t.re())).powf(1.0 / LJ_EXPONENT);
+    let inner = |x: f64| {
+        let u = u_mie(x, LJ_EXPONENT);
+        ((-t_inv * (u - u_c)).exp() - (-t_inv * u).exp()) * x.powi(-k)
+    };
+    // beyond the cutoff with the substitution y = 1 / x
+    let outer = |y: f64| -(-t_inv * u_mie(y.recip(), LJ_EXPONENT)).exp_m1() * y.powi(k - 2);
+    gauss(&inner, x0, 1.0)
+        + gauss(&inner, 1.0, 1.5)
+        + gauss(&inner, 1.5, cutoff)
+        + gauss(&outer, 0.0, cutoff.recip())
+}
+
+/// Packing fraction of the segments.
+fn packing_fraction<D: DualNum<f64>>(
+    p: &PetsParameters,
+    diameter: &Array1<D>,
+    density: ArrayView1<D>,
+) -> D {
+    Zip::from(density)
+        .and(diameter)
+        .and(&p.m)
+        .fold(D::zero(), |acc, &rho, &d, &m| {
+            acc + rho * d.powi(3) * (m * FRAC_PI_6)
+        })
+}
+
+/// Pade approximation `phi2 / (1 - phi3 / phi2)` of the perturbation expansion.
+fn pade<D: DualNum<f64>>(phi2: D, phi3: D) -> D {
+    if phi2.re() == 0.0 {
+        return D::zero();
+    }
+    phi2 * phi2 / (phi2 - phi3)
+}
+
+/// Reduced Helmholtz energy density of the dipole-dipole interactions.
+pub(crate) fn dipole_helmholtz_energy_density<D: DualNum<f64>>(
+    p: &PetsParameters,
+    options: &PetsOptions,
+    temperature: D,
+    diameter: &Array1<D>,
+    density: ArrayView1<D>,
+) -> D {
+    let dipoles: Vec<_> = (0..p.mu.len()).filter(|&i| p.mu[i] != 0.0).collect();
+    let eta = packing_fraction(p, diameter, density);
+    let (sigma, _) = p.effective_parameters(temperature, options.feynman_hibbs);
+    let (sigma_ij, epsilon_k_ij) = p.effective_cross_parameters(temperature, options.feynman_hibbs);
+
+    // reduced squared dipole moments divided by the temperature
+    let t_inv = temperature.recip();
+    let mu2: Array1<D> = Array::from_shape_fn(p.mu.len(), |i| {
+        t_inv / (sigma[i].powi(3) * p.m[i]) * (p.mu[i].powi(2) * DEBYE2)
+    });
+
+    let cutoff = shifted_cutoff(options);
+
+    let mut phi2 = D::zero();
+    let mut phi3 = D::zero();
+    for &i in &dipoles {
+        for &j in &dipoles {
+            let m_ij = (p.m[i] * p.m[j]).sqrt();
+            let epsilon_t = t_inv * epsilon_k_ij[(i, j)];
+            let j2 = pair_integral(&AD, &BD, eta, m_ij, epsilon_t)
+                + truncation_correction(4, epsilon_t.recip(), cutoff) * (2.0 / 3.0);
+            phi2 -= density[i] * density[j] * mu2[i] * mu2[j] * j2 * (sigma[i] * sigma[j]).powi(3)
+                / sigma_ij[(i, j)].powi(3);
+            for &k in &dipoles {
+                let m_ijk = (p.m[i] * p.m[j] * p.m[k]).cbrt();
+                phi3 -= density[i]
+                    * density[j]
+                    * density[k]
+                    * mu2[i]
+                    * mu2[j]
+                    * mu2[k]
+                    * triplet_integral(&CD, eta, m_ijk)
+                    * (sigma[i] * sigma[j] * sigma[k]).powi(3)
+                    / (sigma_ij[(i, j)] * sigma_ij[(i, k)] * sigma_ij[(j, k)]);
+            }
+        }
+    }
+    pade(phi2 * PI, phi3 * (4.0 / 3.0 * PI * PI))
+}
+
+/// Reduced Helmholtz energy density of the quadrupole-quadrupole interactions.
+pub(crate) fn quadrupole_helmholtz_energy_density<D: DualNum<f64>>(
+    p: &PetsParameters,
+    options: &PetsOptions,
+    temperature: D,
+    diameter: &Array1<D>,
+    density: ArrayView1<D>,
+) -> D {
+    let quadrupoles: Vec<_> = (0..p.q.len()).filter(|&i| p.q[i] != 0.0).collect();
+    let eta = packing_fraction(p, diameter, density);
+    let (sigma, _) = p.effective_parameters(temperature, options.feynman_hibbs);
+    let (sigma_ij, epsilon_k_ij) = p.effective_cross_parameters(temperature, options.feynman_hibbs);
+
+    // reduced squared quadrupole moments divided by the temperature
+    let t_inv = temperature.recip();
+    let q2: Array1<D> = Array::from_shape_fn(p.q.len(), |i| {
+        t_inv / (sigma[i].powi(5) * p.m[i]) * (p.q[i].powi(2) * DEBYE2)
+    });
+
+    let cutoff = shifted_cutoff(options);
+
+    let mut phi2 = D::zero();
+    let mut phi3 = D::zero();
+    for &i in &quadrupoles {
+        for &j in &quadrupoles {
+            let m_ij = (p.m[i] * p.m[j]).sqrt();
+            let epsilon_t = t_inv * epsilon_k_ij[(i, j)];
+            let j2 = pair_integral(&AQ, &BQ, eta, m_ij, epsilon_t)
+                + truncation_correction(8, epsilon_t.recip(), cutoff) * (224.0 / 45.0);
+            phi2 -= density[i] * density[j] * q2[i] * q2[j] * j2 * (sigma[i] * sigma[j]).powi(5)
+                / sigma_ij[(i, j)].powi(7);
+            for &k in &quadrupoles {
+                let m_ijk = (p.m[i] * p.m[j] * p.m[k]).cbrt();
+                phi3 += density[i]
+                    * density[j]
+                    * density[k]
+                    * q2[i]
+                    * q2[j]
+                    * q2[k]
+                    * triplet_integral(&CQ, eta, m_ijk)
+                    * (sigma[i] * sigma[j] * sigma[k]).powi(5)
+                    / (sigma_ij[(i, j)] * sigma_ij[(i, k)] * sigma_ij[(j, k)]).powi(3);
+            }
+        }
+    }
+    pade(
+        phi2 * (PI * 0.75f64.powi(2)),
+        phi3 * (PI * PI * 0.75f64.powi(3)),
+    )
+}
+
+/// Reduced Helmholtz energy density of the dipole-quadrupole interactions.
+pub(crate) fn dipole_quadrupole_helmholtz_energy_density<D: DualNum<f64>>(
+    p: &PetsParameters,
+    options: &PetsOptions,
+    temperature: D,
+    diameter: &Array1<D>,
+    density: ArrayView1<D>,
+) -> D {
+    let dipoles: Vec<_> = (0..p.mu.len()).filter(|&i| p.mu[i] != 0.0).collect();
+    let quadrupoles: Vec<_> = (0..p.q.len()).filter(|&i| p.q[i] != 0.0).collect();
+    let polar: Vec<_> = (0..p.mu.len())
+        .filter(|&i| p.mu[i] != 0.0 || p.q[i] != 0.0)
+        .collect();
+    let eta = packing_fraction(p, diameter, density);
+    let (sigma, _) = p.effective_parameters(temperature, options.feynman_hibbs);
+    let (sigma_ij, epsilon_k_ij) = p.effective_cross_parameters(temperature, options.feynman_hibbs);
+
+    // reduced squared dipole and quadrupole moments divided by the temperature
+    let t_inv = temperature.recip();
+    let mu2: Array1<D> = Array::from_shape_fn(p.mu.len(), |i| {
+        t_inv / (sigma[i].powi(3) * p.m[i]) * (p.mu[i].powi(2) * DEBYE2)
+    });
+    let q2: Array1<D> = Array::from_shape_fn(p.q.len(), |i| {
+        t_inv / (sigma[i].powi(5) * p.m[i]) * (p.q[i].powi(2) * DEBYE2)
+    });
+
+    let cutoff = shifted_cutoff(options);
+
+    let mut phi2 = D::zero();
+    let mut phi3 = D::zero();
+    for &i in &dipoles {
+        for &j in &quadrupoles {
+            let m_ij = (p.m[i] * p.m[j]).sqrt();
+            let epsilon_t = t_inv * epsilon_k_ij[(i, j)];
+            let j2 = pair_integral(&ADQ, &BDQ, eta, m_ij, epsilon_t)
+                + truncation_correction(6, epsilon_t.recip(), cutoff) * (8.0 / 3.0);
+            phi2 -= density[i] * density[j] * mu2[i] * q2[j] * j2 * (sigma[i] * sigma[j]).powi(4)
+                / sigma_ij[(i, j)].powi(5);
+            for &k in &polar {
+                let m_ijk = (p.m[i] * p.m[j] * p.m[k]).cbrt();
+                phi3 -= density[i]
+                    * density[j]
+                    * density[k]
+                    * mu2[i]
+                    * q2[j]
+                    * (mu2[k] + q2[k] * 1.19374)
+                    * triplet_integral(&CDQ, eta, m_ijk)
+                    * (sigma[i] * sigma[j] * sigma[k]).powi(4)
+                    / (sigma_ij[(i, j)] * sigma_ij[(i, k)] * sigma_ij[(j, k)]).powi(2);
+            }
+        }
+    }
+    pade(phi2 * (PI * 2.25), phi3)
+}
+
+/// Dipole-dipole contribution of Gross and Vrabec (2006).
+///
+/// The coefficients were adjusted to the full Lennard-Jones potential. For the
+/// truncated and shifted potential, the zero-density term of the pair integral
+/// is corrected so that the second virial coefficient of the truncated and
+/// shifted Stockmayer fluid is exact; the density dependence and the
+/// three-body term are those of the full potential.
+#[derive(Debug, Clone)]
+pub struct Dipole {
+    pub parameters: Rc<PetsParameters>,
+    pub options: PetsOptions,
+}
+
+impl<D: DualNum<f64>> HelmholtzEnergyDual<D> for Dipole {
+    fn helmholtz_energy(&self, state: &StateHD<D>) -> D {
+        let p = &self.parameters;
+        let d = p.hs_diameter(state.temperature, &self.options);
+        dipole_helmholtz_energy_density(
+            p,
+            &self.options,
+            state.temperature,
+            &d,
+            state.partial_density.view(),
+        ) * state.volume
+    }
+}
+
+impl fmt::Display for Dipole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Dipole")
+    }
+}
+
+/// Quadrupole-quadrupole contribution of Gross (2005).
+///
+/// As for the dipole term, the coefficients of the full Lennard-Jones potential
+/// are used with the zero-density correction for the truncated and shifted
+/// potential. Cross interactions between dipoles and quadrupoles are
+/// described by [`DipoleQuadrupole`].
+#[derive(Debug, Clone)]
+pub struct Quadrupole {
+    pub parameters: Rc<PetsParameters>,
+    pub options: PetsOptions,
+}
+
+impl<D: DualNum<f64>> HelmholtzEnergyDual<D> for Quadrupole {
+    fn helmholtz_energy(&self, state: &StateHD<D>) -> D {
+        let p = &self.parameters;
+        let d = p.hs_diameter(state.temperature, &self.options);
+        quadrupole_helmholtz_energy_density(
+            p,
+            &self.options,
+            state.temperature,
+            &d,
+            state.partial_density.view(),
+        ) * state.volume
+    }
+}
+
+impl fmt::Display for Quadrupole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Quadrupole")
+    }
+}
+
+/// Dipole-quadrupole contribution of Vrabec and Gross (2008).
+///
+/// The contribution vanishes unless the fluid contains both dipoles and
+/// quadrupoles. As for the dipole and quadrupole terms, the coefficients of
+/// the full Lennard-Jones potential are used with the zero-density correction
+/// for the truncated and shifted potential.
+#[derive(Debug, Clone)]
+pub struct DipoleQuadrupole {
+    pub parameters: Rc<PetsParameters>,
+    pub options: PetsOptions,
+}
+
+impl<D: DualNum<f64>> HelmholtzEnergyDual<D> for DipoleQuadrupole {
+    fn helmholtz_energy(&self, state: &StateHD<D>) -> D {
+        let p = &self.parameters;
+        let d = p.hs_diameter(state.temperature, &self.options);
+        dipole_quadrupole_helmholtz_energy_density(
+            p,
+            &self.options,
+            state.temperature,
+            &d,
+            state.partial_density.view(),
+        ) * state.volume
+    }
+}
+
+impl fmt::Display for DipoleQuadrupole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Dipole-quadrupole")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eos::Pets;
+    use crate::parameters::utils::{argon_krypton_parameters, argon_parameters, map_model_records};
+    use crate::parameters::{CombiningRule, PetsBinaryRecord};
+    use approx::assert_relative_eq;
+    use feos_core::parameter::Parameter;
+    use feos_core::{Contributions, EquationOfState, SolverOptions, State};
+    use quantity::si::{KELVIN, METER, MOL};
+
+    #[test]
+    fn mix() {
+        let t = 250.0;
+        let v = 1e5;
+        let n = 1000.0;
+        let pure = Dipole {
+            parameters: map_model_records(&argon_parameters(), |_, r| r.with_dipole_moment(1.5)),
+            options: PetsOptions::default(),
+        }
+        .helmholtz_energy(&StateHD::new(t, v, arr1(&[n])));
+        let mix = Dipole {
+            parameters: map_model_records(&argon_krypton_parameters(), |i, r| {
+                r.with_dipole_moment([1.5, 0.0][i])
+            }),
+            options: PetsOptions::default(),
+        };
+        assert!(pure < 0.0);
+        assert_relative_eq!(
+            pure,
+            mix.helmholtz_energy(&StateHD::new(t, v, arr1(&[n, 0.0]))),
+            max_relative = 1e-14
+        );
+        assert_eq!(
+            mix.helmholtz_energy(&StateHD::new(t, v, arr1(&[0.0, n]))),
+            0.0
+        );
+    }
+
+    /// Zero-density pair integral `∫ x^-k exp(-u(x) / t) dx` of the Lennard-Jones
+    /// potential (truncated and shifted for a finite cutoff) with the trapezoidal rule.
+    fn zero_density_integral(k: i32, t: f64, cutoff: f64) -> f64 {
+        let points = 200000;
+        let trapezoid = |f: &dyn Fn(f64) -> f64, a: f64, b: f64| {
+            let dx = (b - a) / points as f64;
+            (0..=points)
+                .map(|i| {
+                    let w = if i == 0 || i == points { 0.5 } else { 1.0 };
+                    w * f(a + i as f64 * dx) * dx
+                })
+                .sum::<f64>()
+        };
+        let boltzmann = |x: f64| (-u_mie(x, LJ_EXPONENT) / t).exp();
+        let rc = cutoff.min(2.5);
+        let shift = if cutoff.is_finite() {
+            (u_mie(cutoff, LJ_EXPONENT) / t).exp()
+        } else {
+            1.0
+        };
+        let inner = trapezoid(&|x| x.powi(-k) * boltzmann(x) * shift, 0.5, rc);
+        let outer = if cutoff.is_finite() {
+            rc.powi(1 - k) / (k - 1) as f64
+        } else {
+            trapezoid(
+                &|y| {
+                    if y > 0.0 {
+                        y.powi(k - 2) * boltzmann(1.0 / y)
+                    } else {
+                        0.0
+                    }
+                },
+                0.0,
+                1.0 / rc,
+            )
+        };
+        inner + outer
+    }
+
+    #[test]
+    fn truncation_correction_numerical() {
+        for k in [4, 6, 8] {
+            for t in [0.7, 1.0, 2.0, 5.0] {
+                assert_relative_eq!(
+                    truncation_correction(k, t, 2.5),
+                    zero_density_integral(k, t, 2.5) - zero_density_integral(k, t, f64::INFINITY),
+                    max_relative = 1e-6
+                );
+            }
+            assert_eq!(truncation_correction(k, 1.0, f64::INFINITY), 0.0);
+        }
+    }
+
+    #[test]
+    fn low_density_limit() {
+        // only the second-order term with the leading coefficients remains,
+        // corrected by the exact zero-density integrals for the truncated potential
+        let parameters = map_model_records(&argon_parameters(), |_, r| r.with_dipole_moment(1.5));
+        let t = 300.0;
+        let n = 1.0;
+        let v = 1e12;
+        let sigma = parameters.sigma[0];
+        let epsilon_k = parameters.epsilon_k[0];
+        let mu2 = 1.5f64.powi(2) * DEBYE2 / (epsilon_k * sigma.powi(3));
+        for tail_correction in [false, true] {
+            let options = PetsOptions {
+                tail_correction,
+                ..Default::default()
+            };
+            let a = Dipole {
+                parameters: parameters.clone(),
+                options,
+            }
+            .helmholtz_energy(&StateHD::new(t, v, arr1(&[n])));
+            let mut j2 = AD[0][0] + BD[0][0] * epsilon_k / t;
+            if !tail_correction {
+                let t_red = t / epsilon_k;
+                j2 += 2.0 / 3.0
+                    * (zero_density_integral(4, t_red, 2.5)
+                        - zero_density_integral(4, t_red, f64::INFINITY));
+            }
+            assert_relative_eq!(
+                a * v / n.powi(2),
+                -PI * (epsilon_k / t).powi(2) * sigma.powi(3) * mu2.powi(2) * j2,
+                max_relative = 1e-7
+            );
+        }
+    }
+
+    #[test]
+    fn low_density_limit_mixture() {
+        // the cross term uses the cross parameters of the combining rule and l_ij
+        let pure_records = map_model_records(&argon_krypton_parameters(), |i, r| {
+            r.with_dipole_moment([1.5, 1.0][i])
+        })
+        .pure_records
+        .clone();
+        let parameters = Rc::new(
+            PetsParameters::new_binary(pure_records, Some(PetsBinaryRecord::new(0.0, 0.0, 0.05)))
+                .with_combining_rule(CombiningRule::Kong),
+        );
+        let t = 300.0;
+        let n = [0.3, 0.7];
+        let v = 1e12;
+        // without the truncation correction
+        let options = PetsOptions {
+            tail_correction: true,
+            ..Default::default()
+        };
+        let a = Dipole {
+            parameters: parameters.clone(),
+            options,
+        }
+        .helmholtz_energy(&StateHD::new(t, v, arr1(&n)));
+        let mut expected = 0.0;
+        for i in 0..2 {
+            for j in 0..2 {
+                let mu2 = |k: usize| {
+                    parameters.mu[k].powi(2) * DEBYE2 / (t * parameters.sigma[k].powi(3))
+                };
+                let sigma_ij = parameters.sigma_ij[[i, j]];
+                let j2 = AD[0][0] + BD[0][0] * parameters.e_k_ij[[i, j]] / t;
+                expected -= PI
+                    * n[i]
+                    * n[j]
+                    * mu2(i)
+                    * mu2(j)
+                    * j2
+                    * (parameters.sigma[i] * parameters.sigma[j]).powi(3)
+                    / sigma_ij.powi(3);
+            }
+        }
+        assert!(parameters.sigma_ij[[0, 1]] < 0.96 * 0.5 * (3.405 + 3.63));
+        assert_relative_eq!(a * v, expected, max_relative = 1e-8);
+    }
+
+    #[test]
+    fn critical_temperature() {
+        let tc: Vec<f64> = [0.0, 1.0, 2.0]
+            .iter()
+            .map(|&mu| {
+                let parameters =
+                    map_model_records(&argon_parameters(), |_, r| r.with_dipole_moment(mu));
+                let eos = Rc::new(Pets::new(parameters));
+                State::critical_point(&eos, None, None, SolverOptions::default())
+                    .unwrap()
+                    .temperature
+                    .to_reduced(KELVIN)
+                    .unwrap()
+            })
+            .collect();
+        assert!(tc[0] < tc[1] && tc[1] < tc[2]);
+    }
+
+    #[test]
+    fn quadrupole_mix() {
+        let t = 250.0;
+        let v = 1e5;
+        let n = 1000.0;
+        let pure = Quadrupole {
+            parameters: map_model_records(&argon_parameters(), |_, r| {
+                r.with_quadrupole_moment(4.0)
+            }),
+            options: PetsOptions::default(),
+        }
+        .helmholtz_energy(&StateHD::new(t, v, arr1(&[n])));
+        let mix = Quadrupole {
+            parameters: map_model_records(&argon_krypton_parameters(), |i, r| {
+                r.with_quadrupole_moment([4.0, 0.0][i])
+            }),
+            options: PetsOptions::default(),
+        };
+        assert!(pure < 0.0);
+        assert_relative_eq!(
+            pure,
+            mix.helmholtz_energy(&StateHD::new(t, v, arr1(&[n, 0.0]))),
+            max_relative = 1e-14
+        );
+        assert_eq!(
+            mix.helmholtz_energy(&StateHD::new(t, v, arr1(&[0.0, n]))),
+            0.0
+        );
+    }
+
+    #[test]
+    fn quadrupole_low_density_limit() {
+        let parameters =
+            map_model_records(&argon_parameters(), |_, r| r.with_quadrupole_moment(4.0));
+        let t = 300.0;
+        let n = 1.0;
+        let v = 1e12;
+        let sigma = parameters.sigma[0];
+        let epsilon_k = parameters.epsilon_k[0];
+        let q2 = 4.0f64.powi(2) * DEBYE2 / (epsilon_k * sigma.powi(5));
+        for tail_correction in [false, true] {
+            let options = PetsOptions {
+                tail_correction,
+                ..Default::default()
+            };
+            let a = Quadrupole {
+                parameters: parameters.clone(),
+                options,
+            }
+            .helmholtz_energy(&StateHD::new(t, v, arr1(&[n])));
+            let mut j2 = AQ[0][0] + BQ[0][0] * epsilon_k / t;
+            if !tail_correction {
+                let t_red = t / epsilon_k;
+                j2 += 224.0 / 45.0
+                    * (zero_density_integral(8, t_red, 2.5)
+                        - zero_density_integral(8, t_red, f64::INFINITY));
+            }
+            assert_relative_eq!(
+                a * v / n.powi(2),
+                -PI * 0.5625 * (epsilon_k / t).powi(2) * sigma.powi(3) * q2.powi(2) * j2,
+                max_relative = 1e-7
+            );
+        }
+    }
+
+    #[test]
+    fn vanishing_moments() {
+        let parameters = argon_krypton_parameters();
+        let polar = map_model_records(&parameters, |_, r| {
+            r.with_dipole_moment(0.0).with_quadrupole_moment(0.0)
+        });
+        let t = 250.0;
+        let s = StateHD::new(t, 1e5, arr1(&[300.0, 700.0]));
+        assert_eq!(
+            Dipole {
+                parameters: polar.clone(),
+                options: PetsOptions::default(),
+            }
+            .helmholtz_energy(&s),
+            0.0
+        );
+        assert_eq!(
+            Quadrupole {
+                parameters: polar.clone(),
+                options: PetsOptions::default(),
+            }
+            .helmholtz_energy(&s),
+            0.0
+        );
+        assert_eq!(
+            DipoleQuadrupole {
+                parameters: polar.clone(),
+                options: PetsOptions::default(),
+            }
+            .helmholtz_energy(&s),
+            0.0
+        );
+
+        let t = t * KELVIN;
+        let v = 1e-4 * METER.powi(3);
+        let n = arr1(&[0.3, 0.7]) * MOL;
+        let s_pets = State::new_nvt(&Rc::new(Pets::new(parameters)), t, v, &n).unwrap();
+        let s_polar = State::new_nvt(&Rc::new(Pets::new(polar)), t, v, &n).unwrap();
+        assert_eq!(
+            s_pets.pressure(Contributions::Total),
+            s_polar.pressure(Contributions::Total)
+        );
+    }
+
+    #[test]
+    fn quadrupole_critical_temperature() {
+        let tc: Vec<f64> = [0.0, 1.5, 3.0]
+            .iter()
+            .map(|&q| {
+                let parameters =
+                    map_model_records(&argon_parameters(), |_, r| r.with_quadrupole_moment(q));
+                let eos = Rc::new(Pets::new(parameters));
+                State::critical_point(&eos, None, None, SolverOptions::default())
+                    .unwrap()
+                    .temperature
+                    .to_reduced(KELVIN)
+                    .unwrap()
+            })
+            .collect();
+        assert!(tc[0] < tc[1] && tc[1] < tc[2]);
+    }
+
+    #[test]
+    fn dipole_quadrupole_low_density_limit() {
+        // a dipolar and a quadrupolar component with cross parameters from
+        // the combining rule and l_ij
+        let pure_records = map_model_records(&argon_krypton_parameters(), |i, r| {
+            r.with_dipole_moment([1.5, 0.0][i])
+                .with_quadrupole_moment([0.0, 4.0][i])
+        })
+        .pure_records
+        .clone();
+        let parameters = Rc::new(
+            PetsParameters::new_binary(pure_records, Some(PetsBinaryRecord::new(0.0, 0.0, 0.05)))
+                .with_combining_rule(CombiningRule::Kong),
+        );
+        let t = 300.0;
+        let n = [0.3, 0.7];
+        let v = 1e12;
+        let a = DipoleQuadrupole {
+            parameters: parameters.clone(),
+            options: PetsOptions::default(),
+        }
+        .helmholtz_energy(&StateHD::new(t, v, arr1(&n)));
+        let (s1, s2) = (parameters.sigma[0], parameters.sigma[1]);
+        let mu2 = 1.5f64.powi(2) * DEBYE2 / (t * s1.powi(3));
+        let q2 = 4.0f64.powi(2) * DEBYE2 / (t * s2.powi(5));
+        let t_red = t / parameters.e_k_ij[[0, 1]];
+        let j2 = ADQ[0][0]
+            + BDQ[0][0] / t_red
+            + 8.0 / 3.0
+                * (zero_density_integral(6, t_red, 2.5)
+                    - zero_density_integral(6, t_red, f64::INFINITY));
+        assert!(a < 0.0);
+        assert_relative_eq!(
+            a * v,
+            -2.25 * PI * n[0] * n[1] * mu2 * q2 * j2 * (s1 * s2).powi(4)
+                / parameters.sigma_ij[[0, 1]].powi(5),
+            max_relative = 1e-7
+        );
+    }
+
+    #[test]
+    fn dipole_quadrupole_mixture() {
+        // the dipole-quadrupole term only contributes if both dipoles and
+        // quadrupoles are present and vanishes continuously with the moments
+        let t = 200.0 * KELVIN;
+        let v = 1e-4 * METER.powi(3);
+        let n = arr1(&[0.3, 0.7]) * MOL;
+        let pressure = |mu: f64, q: f64| {
+            let parameters = map_model_records(&argon_krypton_parameters(), |i, r| {
+                r.with_dipole_moment([mu, 0.0][i])
+                    .with_quadrupole_moment([0.0, q][i])
+            });
+            let eos = Rc::new(Pets::new(parameters));
+            let contributions: Vec<_> = eos.residual().iter().map(|c| c.to_string()).collect();
+            let s = State::new_nvt(&eos, t, v, &n).unwrap();
+            (
+                s.pressure(Contributions::Total),
+                contributions.contains(&String::from("Dipole-quadrupole")),
+            )
+        };
+        let (p_pets, _) = pressure(0.0, 0.0);
+        let (p_dipole, dq_dipole) = pressure(1.5, 0.0);
+        let (p_quadrupole, dq_quadrupole) = pressure(0.0, 4.0);
+        let (p_polar, dq_polar) = pressure(1.5, 4.0);
+        let (p_weak, _) = pressure(1e-4, 1e-4);
+        assert!(!dq_dipole && !dq_quadrupole && dq_polar);
+        assert_eq!(p_pets, pressure(0.0, 0.0).0);
+        assert_relative_eq!(p_weak, p_pets, max_relative = 1e-12);
+        // the attractive cross interactions lower the pressure
+        assert!(p_polar < p_dipole + p_quadrupole - p_pets);
+    }
+}
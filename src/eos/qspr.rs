@@ -3,113 +3,137 @@ use feos_core::IdealGasContributionDual;
 use ndarray::Array1;
 use num_dual::*;
 use std::fmt;
-use std::rc::Rc;
 
 const RGAS: f64 = 6.022140857 * 1.38064852;
 const KB: f64 = 1.38064852e-23;
-const T300: f64 = 300.0;
-const T400: f64 = 400.0;
 const T0: f64 = 298.15;
 const P0: f64 = 1.0e5;
 const A3: f64 = 1e-30;
+const DEBYE2: f64 = 1e4 / 1.380649;
 
-// Heat capacity parameters @ T = 300 K (col 1) and T = 400 K (col 2)
-const NA_NP_300: [f64; 6] = [
-    -5763.04893,
-    1232.30607,
-    -239.3513996,
-    0.0,
-    0.0,
-    -15174.28321,
-];
-const NA_NP_400: [f64; 6] = [
-    -8171.26676935062,
-    1498.01217504596,
-    -315.515836223387,
-    0.0,
-    0.0,
-    -19389.5468655708,
-];
-// const NA_P_300: [f64; 6] = [
-//     5177.19095226181,
-//     919.565206504576,
-//     -108.829105648889,
-//     0.0,
-//     -3.93917830677682,
-//     -13504.5671858292,
-// ];
-// const NA_P_400: [f64; 6] = [
-//     10656.1018362315,
-//     1146.10782703748,
-//     -131.023645998081,
-//     0.0,
-//     -9.93789225413177,
-//     -24430.12952497,
-// ];
-// const AP_300: [f64; 6] = [
-//     3600.32322462175,
-//     1006.20461224949,
-//     -151.688378113974,
-//     7.81876773647109e-07,
-//     8.01001754473385,
-//     -8959.37140957179,
-// ];
-// const AP_400: [f64; 6] = [
-//     7248.0697641199,
-//     1267.44346171358,
-//     -208.738557800023,
-//     0.000170238690157906,
-//     -6.7841792685616,
-//     -12669.4196622924,
-// ];
+/// Classical limit of the heat capacity (in units of R) of nonlinear
+/// molecules (translation and rotation).
+const C0: f64 = 4.0;
+/// Heat capacity (in units of R) of spherical molecules (translation).
+const C0_MONATOMIC: f64 = 2.5;
+/// Coefficients of the vibrational heat capacity `c1` (in units of R) for
+/// `1`, `m sigma³` (in Å³), `epsilon_k` (in K), the reduced squared
+/// dipole moment, the association term `kappa_ab (m sigma³)² (exp(epsilon_k_ab / T0) - 1)`
+/// (in Å⁶) and the quadrupole term `m sigma³ |q|` (in Å³ DÅ).
+const C1: [f64; 6] = [-16.355, 0.32210, 0.0099304, -2.8609, 8.7471e-7, 0.0036875];
+/// Coefficients of the characteristic temperature `theta` (in K) for `1`
+/// and `m sigma³` (in Å³).
+const THETA: [f64; 2] = [1649.8, -3.2542];
+/// Lower bound of the characteristic temperature in K.
+const THETA_MIN: f64 = 100.0;
 
+/// Heat capacity `c_p = c0 + c1 E(theta / T)` in J/(mol K) with the
+/// Einstein function `E(x) = x² e^x / (e^x - 1)²`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct EinsteinHeatCapacity {
+    c0: f64,
+    c1: f64,
+    theta: f64,
+}
+
+#[cfg(test)]
+fn einstein(x: f64) -> f64 {
+    let em1 = (-x).exp_m1();
+    x * x * (-x).exp() / (em1 * em1)
+}
+
+impl EinsteinHeatCapacity {
+    /// Heat capacity of component `i` correlated with its parameters.
+    fn new(p: &PetsParameters, i: usize) -> Self {
+        let volume = p.m[i] * p.sigma[i].powi(3);
+        let mu2 = p.mu[i].powi(2) * DEBYE2 / (volume * p.epsilon_k[i]);
+        let association = p.kappa_ab[i] * volume.powi(2) * (p.epsilon_k_ab[i] / T0).exp_m1();
+        let quadrupole = volume * p.q[i].abs();
+        let c1 = (C1[0]
+            + C1[1] * volume
+            + C1[2] * p.epsilon_k[i]
+            + C1[3] * mu2
+            + C1[4] * association
+            + C1[5] * quadrupole)
+            .max(0.0);
+
+        // small, nonpolar and non-associating molecules without vibrational
+        // contribution are treated as spherical
+        let spherical =
+            c1 == 0.0 && p.m[i] == 1.0 && p.mu[i] == 0.0 && p.q[i] == 0.0 && p.kappa_ab[i] == 0.0;
+        let c0 = if spherical { C0_MONATOMIC } else { C0 };
+        Self {
+            c0: c0 * RGAS,
+            c1: c1 * RGAS,
+            theta: (THETA[0] + THETA[1] * volume).max(THETA_MIN),
+        }
+    }
+
+    #[cfg(test)]
+    fn c_p(&self, temperature: f64) -> f64 {
+        if self.c1 == 0.0 {
+            return self.c0;
+        }
+        self.c0 + self.c1 * einstein(self.theta / temperature)
+    }
+
+    /// `h(T) - h(T0) - T (s(T) - s(T0))` in J/mol.
+    fn enthalpy_entropy<D: DualNum<f64>>(&self, temperature: D) -> D {
+        let t = temperature;
+        let mut k = (t - T0 - t * (t / T0).ln()) * self.c0;
+        if self.c1 != 0.0 {
+            // energy and entropy of an Einstein oscillator (in units of R)
+            let x = t.recip() * self.theta;
+            let x0 = self.theta / T0;
+            let u = (-x).exp() / -(-x).exp_m1() * self.theta;
+            let s = x * (-x).exp() / -(-x).exp_m1() - (-(-x).exp_m1()).ln();
+            let u0 = self.theta * (-x0).exp() / -(-x0).exp_m1();
+            let s0 = x0 * (-x0).exp() / -(-x0).exp_m1() - (-(-x0).exp_m1()).ln();
+            k += (u - u0 - t * (s - s0)) * self.c1;
+        }
+        k
+    }
+}
+
+/// QSPR ideal gas model.
+///
+/// The heat capacity is the classical limit of translation and rotation of
+/// nonlinear molecules (`4 R`) plus an Einstein function for the vibrations.
+/// The amplitude of the Einstein function is correlated with the molecular
+/// volume `m sigma³`, `epsilon_k`, the reduced squared dipole moment
+/// `mu² / (m sigma³ epsilon_k)`, the association strength and the
+/// quadrupole moment, its characteristic temperature with the molecular
+/// volume. Small molecules without vibrational contribution (`m = 1`) and
+/// without dipole, quadrupole and association are spherical (`5/2 R`).
+///
+/// The coefficients are fitted to the ideal gas heat capacities (JANAF)
+/// of 16 polyatomic substances between 300 K and 1000 K, with PeTS
+/// parameters (`m = 1`) adjusted to their critical temperatures and
+/// densities. The average absolute deviation is 10 % (maximum 27 %
+/// between 300 K and 600 K and 41 % at 1000 K). The association and
+/// quadrupole coefficients are fitted afterwards to carbon dioxide
+/// (`q = 4.4 DÅ`) and methanol (PC-SAFT association parameters of Gross
+/// and Sadowski). The correlation is valid for `m sigma³` between 19 and
+/// 103 Å³, `epsilon_k` between 115 K and 445 K and dipole moments up to
+/// 2.9 D.
 #[allow(clippy::upper_case_acronyms)]
 pub struct QSPR {
-    pub parameters: Rc<PetsParameters>,
+    heat_capacities: Vec<EinsteinHeatCapacity>,
+}
+
+impl QSPR {
+    pub fn new(parameters: &PetsParameters) -> Self {
+        let heat_capacities = (0..parameters.sigma.len())
+            .map(|i| EinsteinHeatCapacity::new(parameters, i))
+            .collect();
+        Self { heat_capacities }
+    }
 }
 
 impl<D: DualNum<f64>> IdealGasContributionDual<D> for QSPR {
     fn de_broglie_wavelength(&self, temperature: D, components: usize) -> Array1<D> {
-        let (c_300, c_400) = (NA_NP_300, NA_NP_400);
-
         Array1::from_shape_fn(components, |i| {
-            let epsilon_kt = temperature.recip() * self.parameters.epsilon_k[i];
-            let sigma3 = self.parameters.sigma[i].powi(3);
-
-            let p1 = epsilon_kt;
-            let p2 = sigma3;
-            let p3 = epsilon_kt * p2;
-            // let p4 = (temperature.recip() * self.parameters.epsilon_k_ab[i]).exp_m1()
-            //     * p2
-            //     * sigma3
-            //     * self.parameters.kappa_ab[i];
-            // let p5 = p2 * self.parameters.q[i];
-            let p6 = 1.0;
-
-            let icpc300 = (p1 * c_300[0] / T300
-                + p2 * c_300[1]
-                + p3 * c_300[2] / T300
-                // + p4 * c_300[3] / T300
-                // + p5 * c_300[4]
-                + p6 * c_300[5])
-                * 0.001;
-            let icpc400 = (p1 * c_400[0] / T400
-                + p2 * c_400[1]
-                + p3 * c_400[2] / T400
-                // + p4 * c_400[3] / T400
-                // + p5 * c_400[4]
-                + p6 * c_400[5])
-                * 0.001;
-
-            // linear approximation
-            let b = (icpc400 - icpc300) / (T400 - T300);
-            let a = icpc300 - b * T300;
-
-            // integration
-            let k = a * (temperature - T0 - temperature * (temperature / T0).ln())
-                - b * (temperature - T0).powi(2) * 0.5;
-
-            // de Broglie wavelength
+            let k = self.heat_capacities[i].enthalpy_entropy(temperature);
             k / (temperature * RGAS) + (temperature * KB / (P0 * A3)).ln()
         })
     }
@@ -120,3 +144,198 @@ impl fmt::Display for QSPR {
         write!(f, "Ideal gas (QSPR)")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eos::{IdealGasModel, Pets, PetsOptions};
+    use crate::parameters::utils::{argon_parameters, map_model_records};
+    use crate::parameters::PetsRecord;
+    use approx::assert_relative_eq;
+    use feos_core::{Contributions, State};
+    use ndarray::arr1;
+    use quantity::si::{JOULE, KELVIN, METER, MOL};
+    use std::rc::Rc;
+
+    /// Name, sigma, epsilon_k, mu, q and heat capacities of a substance.
+    type Substance = (&'static str, f64, f64, f64, f64, [f64; 3]);
+
+    /// PeTS parameters (sigma in Å, epsilon_k in K, mu in D) fitted to the
+    /// critical point, quadrupole moments (in DÅ) and ideal gas heat
+    /// capacities in J/(mol K) at 300 K, 400 K and 600 K (JANAF) of the
+    /// substances of the fit. Chloromethane, the 16th substance of the fit,
+    /// is omitted because no reference value at 600 K is available.
+    const SUBSTANCES: [Substance; 15] = [
+        (
+            "nitrogen",
+            3.58097,
+            115.872,
+            0.0,
+            1.52,
+            [29.12, 29.25, 30.11],
+        ),
+        ("oxygen", 3.35209, 141.940, 0.0, 0.4, [29.39, 30.11, 32.09]),
+        (
+            "carbon monoxide",
+            3.61691,
+            121.996,
+            0.0,
+            0.0,
+            [29.14, 29.34, 30.44],
+        ),
+        ("methane", 3.69955, 174.978, 0.0, 0.0, [35.75, 40.63, 52.74]),
+        (
+            "carbon dioxide",
+            3.64226,
+            279.262,
+            0.0,
+            4.4,
+            [37.22, 41.33, 47.32],
+        ),
+        (
+            "ethylene",
+            4.06605,
+            259.263,
+            0.0,
+            0.0,
+            [43.06, 53.05, 70.66],
+        ),
+        ("ethane", 4.21473, 280.355, 0.0, 0.0, [52.71, 65.46, 89.19]),
+        ("propane", 4.68264, 339.645, 0.0, 0.0, [73.93, 94.01, 128.7]),
+        (
+            "hydrogen sulfide",
+            3.69232,
+            335.548,
+            0.97,
+            0.0,
+            [34.25, 35.58, 38.88],
+        ),
+        (
+            "sulfur dioxide",
+            3.92675,
+            368.689,
+            1.63,
+            0.0,
+            [39.88, 43.43, 48.98],
+        ),
+        (
+            "ammonia",
+            3.24890,
+            323.428,
+            1.47,
+            0.0,
+            [35.65, 38.71, 45.27],
+        ),
+        ("water", 2.69182, 393.567, 1.85, 0.0, [33.60, 34.26, 36.32]),
+        (
+            "methanol",
+            3.88575,
+            441.709,
+            1.70,
+            0.0,
+            [44.10, 51.42, 67.03],
+        ),
+        (
+            "hydrogen chloride",
+            3.48483,
+            281.737,
+            1.11,
+            0.0,
+            [29.14, 29.18, 29.58],
+        ),
+        ("acetone", 4.50103, 389.946, 2.88, 0.0, [75.0, 92.0, 122.0]),
+    ];
+
+    /// PeTS parameters of a substance with the PC-SAFT association
+    /// parameters of water and methanol (Gross and Sadowski).
+    fn substance(name: &str, sigma: f64, epsilon_k: f64, mu: f64, q: f64) -> Rc<PetsParameters> {
+        let mut record = PetsRecord::new(sigma, epsilon_k, None, None, None)
+            .with_dipole_moment(mu)
+            .with_quadrupole_moment(q);
+        match name {
+            "water" => record = record.with_association(0.034868, 2500.7, 1.0, 1.0),
+            "methanol" => record = record.with_association(0.035176, 2899.5, 1.0, 1.0),
+            _ => (),
+        }
+        map_model_records(&argon_parameters(), |_, _| record.clone())
+    }
+
+    #[test]
+    fn reference_data() {
+        let mut aad = 0.0;
+        for (name, sigma, epsilon_k, mu, q, cp) in SUBSTANCES {
+            let parameters = substance(name, sigma, epsilon_k, mu, q);
+            let model = QSPR::new(&parameters).heat_capacities[0];
+            for (&t, cp) in [300.0, 400.0, 600.0].iter().zip(cp) {
+                let deviation = (model.c_p(t) - cp).abs() / cp;
+                assert!(
+                    deviation < 0.3,
+                    "{} at {} K: {} vs. {}",
+                    name,
+                    t,
+                    model.c_p(t),
+                    cp
+                );
+                aad += deviation / (3 * SUBSTANCES.len()) as f64;
+            }
+        }
+        assert!(aad < 0.11, "average absolute deviation: {}", aad);
+    }
+
+    #[test]
+    fn physical_limits() {
+        for (name, sigma, epsilon_k, mu, q, _) in SUBSTANCES {
+            let parameters = substance(name, sigma, epsilon_k, mu, q);
+            let model = QSPR::new(&parameters).heat_capacities[0];
+            // monotonic between the classical limit and a finite upper bound
+            let mut c = model.c0;
+            assert_relative_eq!(c, 4.0 * RGAS);
+            for t in [1.0, 50.0, 100.0, 200.0, 300.0, 500.0, 1000.0, 5000.0, 1e5] {
+                let c_t = model.c_p(t);
+                assert!(c_t >= c);
+                c = c_t;
+            }
+            assert!(c <= model.c0 + model.c1 * (1.0 + 1e-10));
+        }
+
+        // small spherical molecules result in the translational heat capacity
+        let model = QSPR::new(&argon_parameters()).heat_capacities[0];
+        assert_eq!(model.c_p(1000.0), 2.5 * RGAS);
+
+        // quadrupoles and association increase the vibrational contribution
+        let (_, sigma, epsilon_k, mu, q, _) = SUBSTANCES[4];
+        let polar = QSPR::new(&substance("carbon dioxide", sigma, epsilon_k, mu, q));
+        let nonpolar = QSPR::new(&substance("", sigma, epsilon_k, mu, 0.0));
+        assert!(polar.heat_capacities[0].c1 > nonpolar.heat_capacities[0].c1);
+        let (_, sigma, epsilon_k, mu, q, _) = SUBSTANCES[12];
+        let associating = QSPR::new(&substance("methanol", sigma, epsilon_k, mu, q));
+        let nonassociating = QSPR::new(&substance("", sigma, epsilon_k, mu, q));
+        assert!(associating.heat_capacities[0].c1 > nonassociating.heat_capacities[0].c1);
+    }
+
+    #[test]
+    fn heat_capacity() {
+        let options = PetsOptions {
+            ideal_gas: IdealGasModel::QSPR,
+            ..Default::default()
+        };
+        for parameters in [
+            argon_parameters(),
+            map_model_records(&argon_parameters(), |_, r| {
+                r.with_segments(2.0).with_dipole_moment(1.5)
+            }),
+        ] {
+            let model = QSPR::new(&parameters).heat_capacities[0];
+            let eos = Rc::new(Pets::with_options(parameters, options).unwrap());
+            for t in [150.0, 300.0, 400.0, 700.0] {
+                let s =
+                    State::new_nvt(&eos, t * KELVIN, METER.powi(3), &(arr1(&[1.0]) * MOL)).unwrap();
+                assert_relative_eq!(
+                    s.c_p(Contributions::IdealGas),
+                    model.c_p(t) * JOULE / MOL / KELVIN,
+                    max_relative = 1e-6
+                );
+            }
+        }
+    }
+}
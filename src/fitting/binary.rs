@@ -0,0 +1,482 @@
+use super::{
+    aad, check_length, failed_points, mean_squared_deviation, nelder_mead, relative_deviation,
+    FitOptions,
+};
+use crate::eos::{Pets, PetsOptions};
+use crate::parameters::{PetsBinaryRecord, PetsIdealGasRecord, PetsParameters, PetsRecord};
+use feos_core::parameter::{Parameter, ParameterError, PureRecord};
+use feos_core::{Contributions, EosError, EosResult, PhaseEquilibrium, SolverOptions};
+use ndarray::{arr1, Array1};
+use quantity::si::*;
+use std::rc::Rc;
+
+/// Quantity that is used to measure the deviation between
+/// calculated and experimental phase equilibria.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "python", pyo3::pyclass)]
+pub enum BinaryVleObjective {
+    /// Relative deviation of the bubble point pressure (isothermal data)
+    /// or temperature (isobaric data).
+    BubblePoint,
+    /// Absolute deviation of the vapor mole fraction of the first component.
+    VaporMolefracs,
+}
+
+/// Experimental phase equilibria (Txy or pxy data) of a binary mixture.
+///
+/// Mole fractions refer to the first component. For every data point a
+/// bubble point is calculated at the given liquid composition and the
+/// given temperature (isothermal data) or pressure (isobaric data).
+#[derive(Clone)]
+pub struct BinaryVle {
+    pub temperature: SIArray1,
+    pub pressure: SIArray1,
+    pub liquid_molefracs: Array1<f64>,
+    pub vapor_molefracs: Option<Array1<f64>>,
+    /// Whether the bubble points are calculated at the given pressures.
+    pub isobaric: bool,
+    /// Weight of the data set in the objective function.
+    pub weight: f64,
+}
+
+impl BinaryVle {
+    /// Isothermal phase equilibrium data.
+    pub fn new(
+        temperature: SIArray1,
+        pressure: SIArray1,
+        liquid_molefracs: Array1<f64>,
+        vapor_molefracs: Option<Array1<f64>>,
+    ) -> EosResult<Self> {
+        check_length(&[("temperature", &temperature), ("pressure", &pressure)])?;
+        let n = temperature.len();
+        if liquid_molefracs.len() != n || matches!(&vapor_molefracs, Some(y) if y.len() != n) {
+            return Err(EosError::ParameterError(
+                ParameterError::IncompatibleParameters(format!(
+                    "mole fractions have to be given for all {} data points.",
+                    n
+                )),
+            ));
+        }
+        Ok(Self {
+            temperature,
+            pressure,
+            liquid_molefracs,
+            vapor_molefracs,
+            isobaric: false,
+            weight: 1.0,
+        })
+    }
+
+    /// Isobaric phase equilibrium data.
+    pub fn new_isobaric(
+        temperature: SIArray1,
+        pressure: SIArray1,
+        liquid_molefracs: Array1<f64>,
+        vapor_molefracs: Option<Array1<f64>>,
+    ) -> EosResult<Self> {
+        Ok(Self {
+            isobaric: true,
+            ..Self::new(temperature, pressure, liquid_molefracs, vapor_molefracs)?
+        })
+    }
+
+    /// Bubble point pressures (isothermal data) or temperatures (isobaric
+    /// data) and vapor mole fractions of the first component, NaN for
+    /// bubble points that cannot be calculated.
+    fn bubble_points(&self, eos: &Rc<Pets>) -> (Array1<f64>, Array1<f64>) {
+        let n = self.temperature.len();
+        let mut bubble_point = Array1::from_elem(n, f64::NAN);
+        let mut vapor_molefracs = Array1::from_elem(n, f64::NAN);
+        for i in 0..n {
+            let x = self.liquid_molefracs[i];
+            let (specification, initial) = if self.isobaric {
+                (self.pressure.get(i), self.temperature.get(i))
+            } else {
+                (self.temperature.get(i), self.pressure.get(i))
+            };
+            let vle = PhaseEquilibrium::bubble_point(
+                eos,
+                specification,
+                &arr1(&[x, 1.0 - x]),
+                Some(initial),
+                None,
+                (SolverOptions::default(), SolverOptions::default()),
+            );
+            if let Ok(vle) = vle {
+                let vapor = vle.vapor();
+                let calculated = if self.isobaric {
+                    vapor.temperature.to_reduced(KELVIN)
+                } else {
+                    vapor.pressure(Contributions::Total).to_reduced(PASCAL)
+                };
+                if let Ok(calculated) = calculated {
+                    bubble_point[i] = calculated;
+                    vapor_molefracs[i] = vapor.molefracs[0];
+                }
+            }
+        }
+        (bubble_point, vapor_molefracs)
+    }
+
+    /// Deviations in the bubble point pressure or temperature (relative)
+    /// and, if available, in the vapor mole fractions (absolute).
+    fn deviation(&self, eos: &Rc<Pets>) -> EosResult<Deviation> {
+        let (bubble_point, vapor_molefracs) = self.bubble_points(eos);
+        let experimental = if self.isobaric {
+            self.temperature.to_reduced(KELVIN)?
+        } else {
+            self.pressure.to_reduced(PASCAL)?
+        };
+        Ok((
+            relative_deviation(&bubble_point, &experimental),
+            self.vapor_molefracs.as_ref().map(|y| vapor_molefracs - y),
+        ))
+    }
+}
+
+/// Result of the regression of a binary interaction parameter.
+#[derive(Clone)]
+pub struct BinaryFitResult {
+    /// The fitted binary parameters.
+    pub record: PetsBinaryRecord,
+    /// Average absolute relative deviation of the bubble point pressures
+    /// of the isothermal data.
+    pub aad_pressure: Option<f64>,
+    /// Average absolute relative deviation of the bubble point temperatures
+    /// of the isobaric data.
+    pub aad_temperature: Option<f64>,
+    /// Average absolute deviation of the vapor mole fractions.
+    pub aad_vapor_molefracs: Option<f64>,
+    /// Indices of the bubble points that could not be calculated
+    /// for every data set.
+    pub failed_bubble_points: Vec<Vec<usize>>,
+    /// Number of iterations of the optimizer.
+    pub iterations: usize,
+}
+
+type Deviation = (Array1<f64>, Option<Array1<f64>>);
+
+fn deviations(
+    pure_records: &[PureRecord<PetsRecord, PetsIdealGasRecord>],
+    record: &PetsBinaryRecord,
+    data: &[BinaryVle],
+    options: PetsOptions,
+) -> EosResult<Vec<Deviation>> {
+    let parameters = PetsParameters::new_binary(pure_records.to_vec(), Some(record.clone()));
+    let eos = Rc::new(Pets::with_options(Rc::new(parameters), options)?);
+    data.iter().map(|d| d.deviation(&eos)).collect()
+}
+
+/// Fit the binary interaction parameter `k_ij` of a binary mixture
+/// to experimental phase equilibria.
+///
+/// The parameters in `binary_record` are used as initial values and the
+/// equation of state is evaluated with `eos_options`. If
+/// `temperature_dependent` is true, `k_ij_t` is fitted as well, otherwise
+/// it is kept at its initial value, and the data has to contain at least
+/// two distinct temperatures. All other binary parameters are kept.
+/// The objective function is the sum of the weighted mean squared
+/// deviations of all data sets in the quantity given by `objective`.
+/// Bubble points that cannot be calculated add a large penalty to the
+/// objective function and are reported in the result; errors in the data
+/// or in `eos_options` are returned.
+pub fn fit_binary(
+    pure_records: &[PureRecord<PetsRecord, PetsIdealGasRecord>],
+    binary_record: &PetsBinaryRecord,
+    data: &[BinaryVle],
+    objective: BinaryVleObjective,
+    temperature_dependent: bool,
+    eos_options: PetsOptions,
+    options: FitOptions,
+) -> EosResult<BinaryFitResult> {
+    if pure_records.len() != 2 {
+        return Err(EosError::IncompatibleComponents(pure_records.len(), 2));
+    }
+    if objective == BinaryVleObjective::VaporMolefracs
+        && data.iter().any(|d| d.vapor_molefracs.is_none())
+    {
+        return Err(EosError::ParameterError(
+            ParameterError::InsufficientInformation,
+        ));
+    }
+
+    let mut temperatures = Vec::new();
+    for d in data {
+        temperatures.extend(d.temperature.to_reduced(KELVIN)?);
+    }
+    let t_mean = temperatures.iter().sum::<f64>() / temperatures.len() as f64;
+    if temperature_dependent && temperatures.iter().all(|&t| t == temperatures[0]) {
+        return Err(EosError::ParameterError(
+            ParameterError::IncompatibleParameters(String::from(
+                "fitting a temperature dependent k_ij requires data at two or more distinct temperatures.",
+            )),
+        ));
+    }
+
+    // k_ij_t is varied in units of the mean temperature of the data,
+    // so that the initial step is meaningful also for k_ij_t = 0
+    let binary_record_with = |x: &Array1<f64>| PetsBinaryRecord {
+        k_ij: x[0],
+        k_ij_t: if temperature_dependent {
+            x[1] * t_mean
+        } else {
+            binary_record.k_ij_t
+        },
+        ..binary_record.clone()
+    };
+    let x0 = if temperature_dependent {
+        arr1(&[binary_record.k_ij, binary_record.k_ij_t / t_mean])
+    } else {
+        arr1(&[binary_record.k_ij])
+    };
+
+    let cost = |x: &Array1<f64>| {
+        Ok(
+            deviations(pure_records, &binary_record_with(x), data, eos_options)?
+                .iter()
+                .zip(data)
+                .map(|((dev_bubble, dev_y), d)| {
+                    let dev = match objective {
+                        BinaryVleObjective::BubblePoint => dev_bubble,
+                        BinaryVleObjective::VaporMolefracs => dev_y.as_ref().unwrap(),
+                    };
+                    d.weight * mean_squared_deviation(dev)
+                })
+                .sum(),
+        )
+    };
+    let (x, _, iterations) = nelder_mead(cost, &x0, options)?;
+
+    let record = binary_record_with(&x);
+    let dev = deviations(pure_records, &record, data, eos_options)?;
+    let aad_bubble_point = |isobaric: bool| {
+        let dev: Vec<_> = dev
+            .iter()
+            .zip(data)
+            .filter(|(_, d)| d.isobaric == isobaric)
+            .flat_map(|((dev, _), _)| dev.iter().copied())
+            .collect();
+        (!dev.is_empty()).then(|| aad(&Array1::from(dev)))
+    };
+    let aad_vapor_molefracs = if data.iter().all(|d| d.vapor_molefracs.is_some()) {
+        let dev_y = Array1::from_iter(
+            dev.iter()
+                .flat_map(|(_, dev_y)| dev_y.as_ref().unwrap().iter().copied()),
+        );
+        Some(aad(&dev_y))
+    } else {
+        None
+    };
+    Ok(BinaryFitResult {
+        record,
+        aad_pressure: aad_bubble_point(false),
+        aad_temperature: aad_bubble_point(true),
+        aad_vapor_molefracs,
+        failed_bubble_points: dev.iter().map(|(dev, _)| failed_points(dev)).collect(),
+        iterations,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parameters::utils::argon_krypton_parameters;
+    use crate::parameters::CombiningRule;
+    use approx::assert_relative_eq;
+
+    fn bubble_point_data(
+        pure_records: &[PureRecord<PetsRecord, PetsIdealGasRecord>],
+        record: PetsBinaryRecord,
+        temperature: SINumber,
+        liquid_molefracs: Array1<f64>,
+    ) -> BinaryVle {
+        let parameters = PetsParameters::new_binary(pure_records.to_vec(), Some(record));
+        let eos = Rc::new(Pets::new(Rc::new(parameters)));
+        let vle: Vec<_> = liquid_molefracs
+            .iter()
+            .map(|&x| {
+                PhaseEquilibrium::bubble_point(
+                    &eos,
+                    temperature,
+                    &arr1(&[x, 1.0 - x]),
+                    None,
+                    None,
+                    Default::default(),
+                )
+                .unwrap()
+            })
+            .collect();
+        BinaryVle::new(
+            SIArray1::from_vec(vec![temperature; vle.len()]),
+            SIArray1::from_vec(
+                vle.iter()
+                    .map(|v| v.vapor().pressure(Contributions::Total))
+                    .collect(),
+            ),
+            liquid_molefracs,
+            Some(vle.iter().map(|v| v.vapor().molefracs[0]).collect()),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn fit_argon_krypton() {
+        let pure_records = argon_krypton_parameters().pure_records.clone();
+        let k_ij = 0.05;
+
+        // pseudo-experimental isothermal data from the original parameters
+        let data = bubble_point_data(
+            &pure_records,
+            PetsBinaryRecord::from(k_ij),
+            120.0 * KELVIN,
+            arr1(&[0.2, 0.5, 0.8]),
+        );
+
+        for objective in [
+            BinaryVleObjective::BubblePoint,
+            BinaryVleObjective::VaporMolefracs,
+        ] {
+            let result = fit_binary(
+                &pure_records,
+                &PetsBinaryRecord::from(0.0),
+                std::slice::from_ref(&data),
+                objective,
+                false,
+                PetsOptions::default(),
+                FitOptions::default(),
+            )
+            .unwrap();
+            assert_relative_eq!(result.record.k_ij, k_ij, max_relative = 1e-4);
+            assert!(result.aad_pressure.unwrap() < 1e-5);
+            assert!(result.aad_temperature.is_none());
+            assert!(result.aad_vapor_molefracs.unwrap() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn fit_argon_krypton_temperature_dependent() {
+        let pure_records = argon_krypton_parameters().pure_records.clone();
+        let record = PetsBinaryRecord::new(-0.05, 12.0, 0.0);
+
+        // pseudo-experimental data on two isotherms
+        let data: Vec<_> = [110.0, 125.0]
+            .iter()
+            .map(|&t| {
+                bubble_point_data(&pure_records, record.clone(), t * KELVIN, arr1(&[0.3, 0.7]))
+            })
+            .collect();
+
+        let result = fit_binary(
+            &pure_records,
+            &PetsBinaryRecord::from(0.0),
+            &data,
+            BinaryVleObjective::BubblePoint,
+            true,
+            PetsOptions::default(),
+            FitOptions::default(),
+        )
+        .unwrap();
+        assert_relative_eq!(result.record.k_ij, record.k_ij, max_relative = 1e-3);
+        assert_relative_eq!(result.record.k_ij_t, record.k_ij_t, max_relative = 1e-3);
+        assert!(result.aad_pressure.unwrap() < 1e-5);
+    }
+
+    #[test]
+    fn fit_argon_krypton_failed_bubble_points() {
+        let pure_records = argon_krypton_parameters().pure_records.clone();
+        let k_ij = 0.05;
+        let data = bubble_point_data(
+            &pure_records,
+            PetsBinaryRecord::from(k_ij),
+            120.0 * KELVIN,
+            arr1(&[0.2, 0.5, 0.8]),
+        );
+
+        // no bubble point can be calculated at 1 K
+        let failed = BinaryVle::new(
+            arr1(&[1.0]) * KELVIN,
+            arr1(&[1e-30]) * BAR,
+            arr1(&[0.5]),
+            None,
+        )
+        .unwrap();
+
+        let result = fit_binary(
+            &pure_records,
+            &PetsBinaryRecord::from(0.0),
+            &[data, failed],
+            BinaryVleObjective::BubblePoint,
+            false,
+            PetsOptions::default(),
+            FitOptions::default(),
+        )
+        .unwrap();
+        assert_relative_eq!(result.record.k_ij, k_ij, max_relative = 1e-4);
+        assert!(result.aad_pressure.unwrap() < 1e-5);
+        assert_eq!(result.failed_bubble_points, vec![vec![], vec![0]]);
+    }
+
+    #[test]
+    fn fit_temperature_dependent_single_temperature() {
+        let pure_records = argon_krypton_parameters().pure_records.clone();
+        let data = bubble_point_data(
+            &pure_records,
+            PetsBinaryRecord::from(0.05),
+            120.0 * KELVIN,
+            arr1(&[0.2, 0.5, 0.8]),
+        );
+        assert!(fit_binary(
+            &pure_records,
+            &PetsBinaryRecord::from(0.0),
+            &[data],
+            BinaryVleObjective::BubblePoint,
+            true,
+            PetsOptions::default(),
+            FitOptions::default(),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn fit_argon_krypton_isobaric() {
+        let pure_records = argon_krypton_parameters().pure_records.clone();
+        let record = PetsBinaryRecord {
+            combining_rule: Some(CombiningRule::Kong),
+            ..PetsBinaryRecord::from(0.05)
+        };
+
+        // the bubble points of an isotherm used as isobaric data
+        let data = bubble_point_data(
+            &pure_records,
+            record.clone(),
+            120.0 * KELVIN,
+            arr1(&[0.2, 0.5, 0.8]),
+        );
+        let data = BinaryVle::new_isobaric(
+            data.temperature,
+            data.pressure,
+            data.liquid_molefracs,
+            data.vapor_molefracs,
+        )
+        .unwrap();
+
+        let initial = PetsBinaryRecord {
+            k_ij: 0.0,
+            ..record.clone()
+        };
+        let result = fit_binary(
+            &pure_records,
+            &initial,
+            std::slice::from_ref(&data),
+            BinaryVleObjective::BubblePoint,
+            false,
+            PetsOptions::default(),
+            FitOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(result.record.combining_rule, Some(CombiningRule::Kong));
+        assert_relative_eq!(result.record.k_ij, record.k_ij, max_relative = 1e-4);
+        assert!(result.aad_temperature.unwrap() < 1e-6);
+        assert!(result.aad_pressure.is_none());
+    }
+}
@@ -0,0 +1,201 @@
+//! Regression of PeTS parameters to experimental data.
+use feos_core::parameter::ParameterError;
+use feos_core::{EosError, EosResult};
+use ndarray::{Array1, Zip};
+use quantity::si::SIArray1;
+
+mod binary;
+mod pure;
+mod transport;
+pub use binary::{fit_binary, BinaryFitResult, BinaryVle, BinaryVleObjective};
+pub use pure::{fit_pure, LiquidDensity, PureFitResult, VaporPressure};
+pub use transport::{fit_entropy_scaling, StateVariable, TransportData, TransportFitResult};
+
+/// Options for the parameter regression.
+#[derive(Copy, Clone)]
+pub struct FitOptions {
+    /// Maximum number of iterations of the simplex algorithm.
+    pub max_iter: usize,
+    /// Tolerance for the spread of the objective function in the simplex.
+    ///
+    /// The tolerance is absolute, so that the convergence does not depend
+    /// on the penalties of data points that cannot be calculated.
+    pub tol: f64,
+    /// Relative size of the initial simplex.
+    pub initial_step: f64,
+}
+
+impl Default for FitOptions {
+    fn default() -> Self {
+        Self {
+            max_iter: 500,
+            tol: 1e-10,
+            initial_step: 0.05,
+        }
+    }
+}
+
+/// Check that all data arrays of a data set have the same length.
+fn check_length(data: &[(&str, &SIArray1)]) -> EosResult<()> {
+    let (name0, data0) = data[0];
+    for &(name, d) in &data[1..] {
+        if d.len() != data0.len() {
+            return Err(EosError::ParameterError(
+                ParameterError::IncompatibleParameters(format!(
+                    "{} ({}) and {} ({}) have different lengths.",
+                    name0,
+                    data0.len(),
+                    name,
+                    d.len()
+                )),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Relative deviations between calculated and experimental values.
+fn relative_deviation(calculated: &Array1<f64>, experimental: &Array1<f64>) -> Array1<f64> {
+    Zip::from(calculated)
+        .and(experimental)
+        .map_collect(|&c, &e| (c - e) / e)
+}
+
+/// Squared deviation assigned to data points that cannot be calculated,
+/// e.g., because the phase equilibrium does not converge.
+const PENALTY: f64 = 1e4;
+
+/// Mean squared deviation, with [PENALTY] for the data points
+/// that could not be calculated (NaN).
+fn mean_squared_deviation(deviation: &Array1<f64>) -> f64 {
+    deviation
+        .mapv(|d| if d.is_nan() { PENALTY } else { d * d })
+        .mean()
+        .unwrap_or(0.0)
+}
+
+/// Indices of the data points that could not be calculated (NaN).
+fn failed_points(deviation: &Array1<f64>) -> Vec<usize> {
+    deviation
+        .iter()
+        .enumerate()
+        .filter(|(_, d)| d.is_nan())
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Average absolute deviation of the data points that could be calculated.
+fn aad(deviation: &Array1<f64>) -> f64 {
+    Array1::from_iter(deviation.iter().filter(|d| !d.is_nan()).map(|d| d.abs()))
+        .mean()
+        .unwrap_or(0.0)
+}
+
+/// Minimize `f` using the Nelder-Mead simplex algorithm.
+///
+/// Returns the optimal parameters, the value of the objective function
+/// and the number of iterations. Errors of the objective function, e.g.,
+/// invalid data or options, are returned immediately.
+fn nelder_mead<F: FnMut(&Array1<f64>) -> EosResult<f64>>(
+    mut f: F,
+    x0: &Array1<f64>,
+    options: FitOptions,
+) -> EosResult<(Array1<f64>, f64, usize)> {
+    let n = x0.len();
+
+    // initial simplex
+    let mut simplex: Vec<(Array1<f64>, f64)> = Vec::with_capacity(n + 1);
+    simplex.push((x0.clone(), f(x0)?));
+    for i in 0..n {
+        let mut x = x0.clone();
+        x[i] = if x[i] == 0.0 {
+            options.initial_step
+        } else {
+            x[i] * (1.0 + options.initial_step)
+        };
+        let fx = f(&x)?;
+        simplex.push((x, fx));
+    }
+
+    for k in 0..options.max_iter {
+        simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+        // check for convergence
+        let (best, worst) = (simplex[0].1, simplex[n].1);
+        if worst - best <= options.tol {
+            let (x, fx) = simplex.swap_remove(0);
+            return Ok((x, fx, k));
+        }
+
+        // centroid of all but the worst point
+        let centroid = simplex[..n]
+            .iter()
+            .fold(Array1::zeros(n), |acc, (x, _)| acc + x)
+            / n as f64;
+
+        // reflection
+        let xr = &centroid + &(&centroid - &simplex[n].0);
+        let fr = f(&xr)?;
+        if fr < simplex[0].1 {
+            // expansion
+            let xe = &centroid + &((&xr - &centroid) * 2.0);
+            let fe = f(&xe)?;
+            simplex[n] = if fe < fr { (xe, fe) } else { (xr, fr) };
+        } else if fr < simplex[n - 1].1 {
+            simplex[n] = (xr, fr);
+        } else {
+            // contraction
+            let xc = if fr < simplex[n].1 {
+                &centroid + &((&xr - &centroid) * 0.5)
+            } else {
+                &centroid + &((&simplex[n].0 - &centroid) * 0.5)
+            };
+            let fc = f(&xc)?;
+            if fc < fr.min(simplex[n].1) {
+                simplex[n] = (xc, fc);
+            } else {
+                // shrink towards the best point
+                let x_best = simplex[0].0.clone();
+                for (x, fx) in simplex.iter_mut().skip(1) {
+                    *x = &x_best + &((&*x - &x_best) * 0.5);
+                    *fx = f(x)?;
+                }
+            }
+        }
+    }
+    Err(EosError::NotConverged(String::from("nelder_mead")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use ndarray::arr1;
+
+    #[test]
+    fn penalty() {
+        let deviation = arr1(&[0.1, f64::NAN, -0.3]);
+        assert_relative_eq!(
+            mean_squared_deviation(&deviation),
+            (0.01 + PENALTY + 0.09) / 3.0
+        );
+        assert_relative_eq!(aad(&deviation), 0.2);
+        assert_eq!(failed_points(&deviation), vec![1]);
+    }
+
+    #[test]
+    fn rosenbrock() {
+        let (x, fx, _) = nelder_mead(
+            |x| Ok((1.0 - x[0]).powi(2) + 100.0 * (x[1] - x[0].powi(2)).powi(2)),
+            &arr1(&[-1.2, 1.0]),
+            FitOptions {
+                max_iter: 2000,
+                tol: 1e-16,
+                initial_step: 0.1,
+            },
+        )
+        .unwrap();
+        assert_relative_eq!(x, arr1(&[1.0, 1.0]), epsilon = 1e-5);
+        assert!(fx < 1e-10);
+    }
+}
@@ -0,0 +1,364 @@
+use super::{
+    aad, check_length, failed_points, mean_squared_deviation, nelder_mead, relative_deviation,
+    FitOptions,
+};
+use crate::eos::{Pets, PetsOptions};
+use crate::parameters::{PetsIdealGasRecord, PetsParameters, PetsRecord};
+use feos_core::parameter::{Parameter, PureRecord};
+use feos_core::{
+    Contributions, DensityInitialization, EosResult, EosUnit, PhaseEquilibrium, SolverOptions,
+    State,
+};
+use ndarray::{arr1, Array1};
+use quantity::si::*;
+use std::rc::Rc;
+
+/// Experimental vapor pressures of a pure substance.
+#[derive(Clone)]
+pub struct VaporPressure {
+    pub temperature: SIArray1,
+    pub pressure: SIArray1,
+    /// Weight of the data set in the objective function.
+    pub weight: f64,
+}
+
+impl VaporPressure {
+    pub fn new(temperature: SIArray1, pressure: SIArray1) -> EosResult<Self> {
+        check_length(&[("temperature", &temperature), ("pressure", &pressure)])?;
+        Ok(Self {
+            temperature,
+            pressure,
+            weight: 1.0,
+        })
+    }
+
+    /// Relative deviations of the calculated vapor pressures and the
+    /// indices of the data points above the critical temperature of the model.
+    ///
+    /// Above the critical temperature, no vapor pressure exists. The
+    /// deviations of these points are evaluated with the critical pressure,
+    /// so that the objective function still favors parameters with a higher
+    /// critical temperature. The deviations of vapor pressures that cannot
+    /// be calculated are NaN.
+    fn relative_deviation(&self, eos: &Rc<Pets>) -> EosResult<(Array1<f64>, Vec<usize>)> {
+        let experimental = self.pressure.to_reduced(PASCAL)?;
+        let max_temperature = self
+            .temperature
+            .to_reduced(KELVIN)?
+            .fold(f64::NEG_INFINITY, |a, &b| a.max(b))
+            * KELVIN;
+        let critical_point =
+            State::critical_point(eos, None, Some(max_temperature), SolverOptions::default()).ok();
+
+        let mut supercritical = Vec::new();
+        let calculated = Array1::from_shape_fn(self.temperature.len(), |i| {
+            let t = self.temperature.get(i);
+            let p = match &critical_point {
+                Some(cp) if t >= cp.temperature => {
+                    supercritical.push(i);
+                    Ok(cp.pressure(Contributions::Total))
+                }
+                _ => PhaseEquilibrium::pure(eos, t, None, SolverOptions::default())
+                    .map(|vle| vle.vapor().pressure(Contributions::Total)),
+            };
+            p.ok()
+                .and_then(|p| p.to_reduced(PASCAL).ok())
+                .unwrap_or(f64::NAN)
+        });
+        Ok((
+            relative_deviation(&calculated, &experimental),
+            supercritical,
+        ))
+    }
+}
+
+/// Experimental liquid densities of a pure substance.
+#[derive(Clone)]
+pub struct LiquidDensity {
+    pub temperature: SIArray1,
+    pub pressure: SIArray1,
+    pub density: SIArray1,
+    /// Weight of the data set in the objective function.
+    pub weight: f64,
+}
+
+impl LiquidDensity {
+    pub fn new(temperature: SIArray1, pressure: SIArray1, density: SIArray1) -> EosResult<Self> {
+        check_length(&[
+            ("temperature", &temperature),
+            ("pressure", &pressure),
+            ("density", &density),
+        ])?;
+        Ok(Self {
+            temperature,
+            pressure,
+            density,
+            weight: 1.0,
+        })
+    }
+
+    /// Relative deviations of the calculated liquid densities,
+    /// NaN for densities that cannot be calculated.
+    fn relative_deviation(&self, eos: &Rc<Pets>) -> EosResult<Array1<f64>> {
+        let experimental = self.density.to_reduced(SIUnit::reference_density())?;
+        let moles = arr1(&[1.0]) * MOL;
+        let calculated = Array1::from_shape_fn(self.temperature.len(), |i| {
+            State::new_npt(
+                eos,
+                self.temperature.get(i),
+                self.pressure.get(i),
+                &moles,
+                DensityInitialization::Liquid,
+            )
+            .ok()
+            .and_then(|s| s.density.to_reduced(SIUnit::reference_density()).ok())
+            .unwrap_or(f64::NAN)
+        });
+        Ok(relative_deviation(&calculated, &experimental))
+    }
+}
+
+/// Result of the regression of pure component parameters.
+#[derive(Clone)]
+pub struct PureFitResult {
+    /// The fitted PeTS parameters.
+    pub record: PetsRecord,
+    /// Average absolute relative deviation of the vapor pressures
+    /// that could be calculated.
+    pub aad_vapor_pressure: f64,
+    /// Average absolute relative deviation of the liquid densities
+    /// that could be calculated.
+    pub aad_liquid_density: f64,
+    /// Indices of the vapor pressures above the critical temperature
+    /// of the model or that could not be calculated.
+    pub failed_vapor_pressures: Vec<usize>,
+    /// Indices of the liquid densities that could not be calculated.
+    pub failed_liquid_densities: Vec<usize>,
+    /// Number of iterations of the optimizer.
+    pub iterations: usize,
+}
+
+fn pure_record_with(
+    record: &PureRecord<PetsRecord, PetsIdealGasRecord>,
+    sigma: f64,
+    epsilon_k: f64,
+) -> PureRecord<PetsRecord, PetsIdealGasRecord> {
+    let mut record = record.clone();
+    record.model_record.sigma = sigma;
+    record.model_record.epsilon_k = epsilon_k;
+    record
+}
+
+type Deviation = (Array1<f64>, Vec<usize>, Array1<f64>);
+
+fn deviations(
+    record: &PureRecord<PetsRecord, PetsIdealGasRecord>,
+    vapor_pressure: &VaporPressure,
+    liquid_density: &LiquidDensity,
+    options: PetsOptions,
+) -> EosResult<Deviation> {
+    let parameters = Rc::new(PetsParameters::new_pure(record.clone()));
+    let eos = Rc::new(Pets::with_options(parameters, options)?);
+    let (dev_p, failed) = vapor_pressure.relative_deviation(&eos)?;
+    Ok((dev_p, failed, liquid_density.relative_deviation(&eos)?))
+}
+
+/// Fit the PeTS parameters `sigma` and `epsilon_k` of a pure substance
+/// to experimental vapor pressures and liquid densities.
+///
+/// The parameters in `record` are used as initial values and the
+/// equation of state is evaluated with `eos_options`. The objective
+/// function is the sum of the weighted mean squared relative deviations
+/// of both data sets. The parameters are varied on a logarithmic scale,
+/// so they remain positive. Data points that cannot be calculated add a
+/// large penalty to the objective function and are reported in the
+/// result; errors in the data or in `eos_options` are returned.
+pub fn fit_pure(
+    record: &PureRecord<PetsRecord, PetsIdealGasRecord>,
+    vapor_pressure: &VaporPressure,
+    liquid_density: &LiquidDensity,
+    eos_options: PetsOptions,
+    options: FitOptions,
+) -> EosResult<PureFitResult> {
+    let record_with = |x: &Array1<f64>| pure_record_with(record, x[0].exp(), x[1].exp());
+    let x0 = arr1(&[record.model_record.sigma, record.model_record.epsilon_k]).mapv(f64::ln);
+    let objective = |x: &Array1<f64>| {
+        let (dev_p, _, dev_rho) =
+            deviations(&record_with(x), vapor_pressure, liquid_density, eos_options)?;
+        Ok(vapor_pressure.weight * mean_squared_deviation(&dev_p)
+            + liquid_density.weight * mean_squared_deviation(&dev_rho))
+    };
+    let (x, _, iterations) = nelder_mead(objective, &x0, options)?;
+
+    let fitted = record_with(&x);
+    let (mut dev_p, mut failed, dev_rho) =
+        deviations(&fitted, vapor_pressure, liquid_density, eos_options)?;
+    failed.iter().for_each(|&i| dev_p[i] = f64::NAN);
+    failed.extend(failed_points(&dev_p));
+    failed.sort_unstable();
+    failed.dedup();
+    Ok(PureFitResult {
+        record: fitted.model_record,
+        aad_vapor_pressure: aad(&dev_p),
+        aad_liquid_density: aad(&dev_rho),
+        failed_vapor_pressures: failed,
+        failed_liquid_densities: failed_points(&dev_rho),
+        iterations,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parameters::utils::argon_parameters;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn fit_argon() {
+        let parameters = argon_parameters();
+        let eos = Rc::new(Pets::new(parameters.clone()));
+
+        // pseudo-experimental data from the original parameters
+        let temperature = arr1(&[90.0, 110.0, 130.0]) * KELVIN;
+        let pressure = SIArray1::from_vec(
+            (0..temperature.len())
+                .map(|i| {
+                    PhaseEquilibrium::pure(&eos, temperature.get(i), None, Default::default())
+                        .unwrap()
+                        .vapor()
+                        .pressure(Contributions::Total)
+                })
+                .collect(),
+        );
+        let density = SIArray1::from_vec(
+            (0..temperature.len())
+                .map(|i| {
+                    State::new_npt(
+                        &eos,
+                        temperature.get(i),
+                        10.0 * BAR,
+                        &(arr1(&[1.0]) * MOL),
+                        DensityInitialization::Liquid,
+                    )
+                    .unwrap()
+                    .density
+                })
+                .collect(),
+        );
+        let vapor_pressure = VaporPressure::new(temperature.clone(), pressure).unwrap();
+        let liquid_density =
+            LiquidDensity::new(temperature, arr1(&[10.0, 10.0, 10.0]) * BAR, density).unwrap();
+
+        let initial = pure_record_with(&parameters.pure_records[0], 3.3, 110.0);
+        let result = fit_pure(
+            &initial,
+            &vapor_pressure,
+            &liquid_density,
+            PetsOptions::default(),
+            FitOptions::default(),
+        )
+        .unwrap();
+        assert_relative_eq!(result.record.sigma, 3.405, max_relative = 1e-4);
+        assert_relative_eq!(result.record.epsilon_k, 119.8, max_relative = 1e-4);
+        assert!(result.aad_vapor_pressure < 1e-4);
+        assert!(result.aad_liquid_density < 1e-4);
+        assert!(result.failed_vapor_pressures.is_empty());
+        assert!(result.failed_liquid_densities.is_empty());
+    }
+
+    #[test]
+    fn supercritical_vapor_pressure() {
+        let parameters = argon_parameters();
+        let eos = Rc::new(Pets::new(parameters));
+        let cp = State::critical_point(&eos, None, None, Default::default()).unwrap();
+        let pc = cp.pressure(Contributions::Total);
+
+        let temperature = arr1(&[100.0, 140.0]) * KELVIN;
+        let pressure = SIArray1::from_vec(vec![3.0 * BAR, 60.0 * BAR]);
+        let vapor_pressure = VaporPressure::new(temperature, pressure).unwrap();
+        let (dev, failed) = vapor_pressure.relative_deviation(&eos).unwrap();
+        assert_eq!(failed, vec![1]);
+        assert_relative_eq!(
+            dev[1],
+            (pc.to_reduced(BAR).unwrap() - 60.0) / 60.0,
+            max_relative = 1e-10
+        );
+    }
+
+    #[test]
+    fn fit_with_failed_points() {
+        let parameters = argon_parameters();
+        let eos = Rc::new(Pets::new(parameters.clone()));
+
+        // no vapor pressure can be calculated at 1 K
+        let temperature = arr1(&[100.0, 120.0, 1.0]) * KELVIN;
+        let mut pressure: Vec<_> = [100.0, 120.0]
+            .iter()
+            .map(|&t| {
+                PhaseEquilibrium::pure(&eos, t * KELVIN, None, Default::default())
+                    .unwrap()
+                    .vapor()
+                    .pressure(Contributions::Total)
+            })
+            .collect();
+        pressure.push(1e-30 * BAR);
+        let vapor_pressure = VaporPressure::new(temperature, SIArray1::from_vec(pressure)).unwrap();
+        let (dev, supercritical) = vapor_pressure.relative_deviation(&eos).unwrap();
+        assert!(supercritical.is_empty());
+        assert!(dev[2].is_nan());
+
+        let density = State::new_npt(
+            &eos,
+            100.0 * KELVIN,
+            10.0 * BAR,
+            &(arr1(&[1.0]) * MOL),
+            DensityInitialization::Liquid,
+        )
+        .unwrap()
+        .density;
+        let liquid_density = LiquidDensity::new(
+            arr1(&[100.0]) * KELVIN,
+            arr1(&[10.0]) * BAR,
+            SIArray1::from_vec(vec![density]),
+        )
+        .unwrap();
+
+        let initial = pure_record_with(&parameters.pure_records[0], 3.3, 110.0);
+        let result = fit_pure(
+            &initial,
+            &vapor_pressure,
+            &liquid_density,
+            PetsOptions::default(),
+            FitOptions::default(),
+        )
+        .unwrap();
+        assert_relative_eq!(result.record.sigma, 3.405, max_relative = 1e-4);
+        assert_relative_eq!(result.record.epsilon_k, 119.8, max_relative = 1e-4);
+        assert!(result.aad_vapor_pressure < 1e-4);
+        assert_eq!(result.failed_vapor_pressures, vec![2]);
+        assert!(result.failed_liquid_densities.is_empty());
+    }
+
+    #[test]
+    fn fit_errors() {
+        let parameters = argon_parameters();
+        let temperature = arr1(&[90.0, 110.0]) * KELVIN;
+        let vapor_pressure =
+            VaporPressure::new(temperature.clone(), arr1(&[1.3, 6.6]) * BAR).unwrap();
+        // densities in the wrong units
+        let liquid_density = LiquidDensity::new(
+            temperature,
+            arr1(&[10.0, 10.0]) * BAR,
+            arr1(&[1390.0, 1230.0]) * KILOGRAM / METER.powi(3),
+        )
+        .unwrap();
+        assert!(fit_pure(
+            &parameters.pure_records[0],
+            &vapor_pressure,
+            &liquid_density,
+            PetsOptions::default(),
+            FitOptions::default(),
+        )
+        .is_err());
+    }
+}
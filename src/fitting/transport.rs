@@ -0,0 +1,359 @@
+use super::{aad, check_length};
+use crate::eos::{Pets, PetsOptions};
+use crate::parameters::{PetsIdealGasRecord, PetsParameters, PetsRecord, TransportProperty};
+use feos_core::parameter::{Parameter, ParameterError, PureRecord};
+use feos_core::{
+    Contributions, DensityInitialization, EntropyScaling, EosError, EosResult, EosUnit, State,
+};
+use ndarray::{arr1, Array1, Array2, Axis};
+use num_dual::linalg::LU;
+use quantity::si::*;
+use std::rc::Rc;
+
+/// Pressures or molar densities of the data points.
+#[derive(Clone)]
+pub enum StateVariable {
+    Pressure(SIArray1),
+    Density(SIArray1),
+}
+
+/// Experimental transport properties of a pure substance.
+#[derive(Clone)]
+pub struct TransportData {
+    pub property: TransportProperty,
+    pub temperature: SIArray1,
+    pub state: StateVariable,
+    /// Viscosities, self-diffusion coefficients or thermal conductivities.
+    pub value: SIArray1,
+}
+
+impl TransportData {
+    /// Transport data at given temperatures and pressures.
+    pub fn new(
+        property: TransportProperty,
+        temperature: SIArray1,
+        pressure: SIArray1,
+        value: SIArray1,
+    ) -> EosResult<Self> {
+        check_length(&[
+            ("temperature", &temperature),
+            ("pressure", &pressure),
+            ("value", &value),
+        ])?;
+        Ok(Self {
+            property,
+            temperature,
+            state: StateVariable::Pressure(pressure),
+            value,
+        })
+    }
+
+    /// Transport data at given temperatures and molar densities.
+    pub fn new_density(
+        property: TransportProperty,
+        temperature: SIArray1,
+        density: SIArray1,
+        value: SIArray1,
+    ) -> EosResult<Self> {
+        check_length(&[
+            ("temperature", &temperature),
+            ("density", &density),
+            ("value", &value),
+        ])?;
+        Ok(Self {
+            property,
+            temperature,
+            state: StateVariable::Density(density),
+            value,
+        })
+    }
+
+    fn state(&self, eos: &Rc<Pets>, i: usize) -> EosResult<State<SIUnit, Pets>> {
+        let t = self.temperature.get(i);
+        let moles = arr1(&[1.0]) * MOL;
+        match &self.state {
+            StateVariable::Pressure(p) => {
+                State::new_npt(eos, t, p.get(i), &moles, DensityInitialization::None)
+            }
+            StateVariable::Density(rho) => State::new_nvt(eos, t, MOL / rho.get(i), &moles),
+        }
+    }
+
+    /// Reduced residual entropies and logarithms of the ratios of the
+    /// experimental values and the reference values of the data points.
+    fn entropy_ratio(&self, eos: &Rc<Pets>) -> EosResult<(Array1<f64>, Array1<f64>)> {
+        let mut s_res = Array1::zeros(self.temperature.len());
+        let mut ln_ratio = Array1::zeros(self.temperature.len());
+        for i in 0..self.temperature.len() {
+            let state = self.state(eos, i)?;
+            s_res[i] = state
+                .molar_entropy(Contributions::ResidualNvt)
+                .to_reduced(SIUnit::reference_molar_entropy())?;
+            let (t, v, n) = (state.temperature, state.volume, &state.moles);
+            let reference = match self.property {
+                TransportProperty::Viscosity => eos.viscosity_reference(t, v, n)?,
+                TransportProperty::Diffusion => eos.diffusion_reference(t, v, n)?,
+                TransportProperty::ThermalConductivity => {
+                    eos.thermal_conductivity_reference(t, v, n)?
+                }
+            };
+            ln_ratio[i] = self.value.get(i).to_reduced(reference)?.ln();
+        }
+        Ok((s_res, ln_ratio))
+    }
+}
+
+/// Terms of the entropy scaling correlations in `Pets`.
+fn correlation_terms(property: TransportProperty, s: f64) -> Vec<f64> {
+    match property {
+        TransportProperty::Viscosity => vec![1.0, s, s.powi(2), s.powi(3)],
+        TransportProperty::Diffusion => {
+            vec![1.0, s, -(1.0 - s.exp()) * s.powi(2), -s.powi(4), -s.powi(8)]
+        }
+        TransportProperty::ThermalConductivity => vec![1.0, s, 1.0 - s.exp(), s.powi(2)],
+    }
+}
+
+/// Result of the regression of entropy scaling coefficients.
+#[derive(Clone)]
+pub struct TransportFitResult {
+    /// The PeTS parameters with the fitted coefficients.
+    pub record: PetsRecord,
+    /// Relative deviations of the data points.
+    pub relative_deviation: Array1<f64>,
+    /// Average absolute relative deviation.
+    pub aad: f64,
+    /// Maximum absolute relative deviation.
+    pub max_deviation: f64,
+}
+
+/// Fit the entropy scaling coefficients of a pure substance to
+/// experimental transport properties.
+///
+/// The residual entropies and the reference values are calculated with
+/// `Pets` using the parameters in `record` and the given `options`, which
+/// have to be the options of the equation of state that the coefficients
+/// are used with. The coefficients minimize the sum of squared deviations
+/// of the logarithm of the transport property; the correlations are linear
+/// in the coefficients, so no initial values are required. Thermal
+/// conductivities use the diffusion coefficients of `record` in the
+/// reference.
+pub fn fit_entropy_scaling(
+    record: &PureRecord<PetsRecord, PetsIdealGasRecord>,
+    data: &TransportData,
+    options: PetsOptions,
+) -> EosResult<TransportFitResult> {
+    let property = data.property;
+    let parameters = Rc::new(PetsParameters::new_pure(record.clone()));
+    let eos = Rc::new(Pets::with_options(parameters, options)?);
+    let (s_res, ln_ratio) = data.entropy_ratio(&eos)?;
+
+    let n = correlation_terms(property, 0.0).len();
+    if s_res.len() < n {
+        return Err(EosError::ParameterError(
+            ParameterError::IncompatibleParameters(format!(
+                "{} {} coefficients require at least {} data points.",
+                n, property, n
+            )),
+        ));
+    }
+
+    // least squares with scaled columns
+    let x = Array2::from_shape_fn((s_res.len(), n), |(i, j)| {
+        correlation_terms(property, s_res[i])[j]
+    });
+    let scale = x
+        .map_axis(Axis(0), |c| c.fold(0.0, |a: f64, b| a.max(b.abs())))
+        .mapv(|c| if c > 0.0 { c.recip() } else { 1.0 });
+    let x_scaled = &x * &scale;
+    let coefficients =
+        LU::new(x_scaled.t().dot(&x_scaled))?.solve(&x_scaled.t().dot(&ln_ratio)) * &scale;
+
+    let mut model_record = record.model_record.clone();
+    match property {
+        TransportProperty::Viscosity => {
+            model_record.viscosity = Some([0, 1, 2, 3].map(|i| coefficients[i]))
+        }
+        TransportProperty::Diffusion => {
+            model_record.diffusion = Some([0, 1, 2, 3, 4].map(|i| coefficients[i]))
+        }
+        TransportProperty::ThermalConductivity => {
+            model_record.thermal_conductivity = Some([0, 1, 2, 3].map(|i| coefficients[i]))
+        }
+    }
+
+    let relative_deviation = (x.dot(&coefficients) - &ln_ratio).mapv(f64::exp_m1);
+    Ok(TransportFitResult {
+        record: model_record,
+        aad: aad(&relative_deviation),
+        max_deviation: relative_deviation.fold(0.0, |a, d| a.max(d.abs())),
+        relative_deviation,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eos::IdealGasModel;
+    use crate::parameters::utils::argon_parameters;
+    use approx::assert_relative_eq;
+
+    fn pseudo_experimental_data(
+        record: &PureRecord<PetsRecord, PetsIdealGasRecord>,
+        property: TransportProperty,
+        options: PetsOptions,
+    ) -> (SIArray1, SIArray1, SIArray1) {
+        let parameters = Rc::new(PetsParameters::new_pure(record.clone()));
+        let eos = Rc::new(Pets::with_options(parameters, options).unwrap());
+        let mut temperature = Vec::new();
+        let mut pressure = Vec::new();
+        let mut value = Vec::new();
+        for &t in &[100.0, 150.0, 200.0, 300.0] {
+            for &p in &[1.0, 10.0, 50.0, 200.0] {
+                let s = State::new_npt(
+                    &eos,
+                    t * KELVIN,
+                    p * BAR,
+                    &(arr1(&[1.0]) * MOL),
+                    DensityInitialization::None,
+                )
+                .unwrap();
+                temperature.push(t * KELVIN);
+                pressure.push(p * BAR);
+                value.push(match property {
+                    TransportProperty::Viscosity => s.viscosity().unwrap(),
+                    TransportProperty::Diffusion => s.diffusion().unwrap(),
+                    TransportProperty::ThermalConductivity => s.thermal_conductivity().unwrap(),
+                });
+            }
+        }
+        (
+            SIArray1::from_vec(temperature),
+            SIArray1::from_vec(pressure),
+            SIArray1::from_vec(value),
+        )
+    }
+
+    #[test]
+    fn fit_coefficients() {
+        // pseudo-experimental data from known coefficients
+        let mut record = argon_parameters().pure_records[0].clone();
+        record.model_record.viscosity = Some([-0.1, -0.2, 0.5, 0.1]);
+        record.model_record.diffusion = Some([0.1, 0.2, 0.05, -0.001, 0.0]);
+        record.model_record.thermal_conductivity = Some([-3.0, -1.2, -0.3, -0.05]);
+
+        for property in [
+            TransportProperty::Viscosity,
+            TransportProperty::Diffusion,
+            TransportProperty::ThermalConductivity,
+        ] {
+            let (temperature, pressure, value) =
+                pseudo_experimental_data(&record, property, PetsOptions::default());
+            let data = TransportData::new(property, temperature, pressure, value).unwrap();
+            let mut initial = record.clone();
+            match property {
+                TransportProperty::Viscosity => initial.model_record.viscosity = None,
+                TransportProperty::Diffusion => initial.model_record.diffusion = None,
+                TransportProperty::ThermalConductivity => {
+                    initial.model_record.thermal_conductivity = None
+                }
+            }
+            let result = fit_entropy_scaling(&initial, &data, PetsOptions::default()).unwrap();
+            assert!(result.aad < 1e-8);
+            assert!(result.max_deviation < 1e-8);
+            assert_eq!(result.relative_deviation.len(), 16);
+            let (fitted, expected) = match property {
+                TransportProperty::Viscosity => (
+                    result.record.viscosity.unwrap().to_vec(),
+                    record.model_record.viscosity.unwrap().to_vec(),
+                ),
+                TransportProperty::Diffusion => (
+                    result.record.diffusion.unwrap().to_vec(),
+                    record.model_record.diffusion.unwrap().to_vec(),
+                ),
+                TransportProperty::ThermalConductivity => (
+                    result.record.thermal_conductivity.unwrap().to_vec(),
+                    record.model_record.thermal_conductivity.unwrap().to_vec(),
+                ),
+            };
+            assert_relative_eq!(arr1(&fitted), arr1(&expected), epsilon = 1e-6);
+        }
+    }
+
+    #[test]
+    fn fit_with_options() {
+        let mut record = argon_parameters().pure_records[0].clone();
+        record.model_record.thermal_conductivity = Some([-3.0, -1.2, -0.3, -0.05]);
+        let options = PetsOptions {
+            tail_correction: true,
+            ideal_gas: IdealGasModel::Monatomic,
+            ..Default::default()
+        };
+        let property = TransportProperty::ThermalConductivity;
+        let (temperature, pressure, value) = pseudo_experimental_data(&record, property, options);
+        let data = TransportData::new(property, temperature, pressure, value).unwrap();
+
+        let result = fit_entropy_scaling(&record, &data, options).unwrap();
+        assert!(result.max_deviation < 1e-8);
+        assert_relative_eq!(
+            arr1(&result.record.thermal_conductivity.unwrap()),
+            arr1(&record.model_record.thermal_conductivity.unwrap()),
+            epsilon = 1e-6
+        );
+
+        // the default options result in different coefficients
+        let result = fit_entropy_scaling(&record, &data, PetsOptions::default()).unwrap();
+        assert!(result.aad > 1e-3);
+    }
+
+    #[test]
+    fn density_specification() {
+        let record = argon_parameters().pure_records[0].clone();
+        let eos = Rc::new(Pets::new(Rc::new(PetsParameters::new_pure(record.clone()))));
+        let moles = arr1(&[1.0]) * MOL;
+        let temperature = arr1(&[150.0, 150.0, 200.0, 200.0, 300.0]) * KELVIN;
+        let density = arr1(&[1.0, 10.0, 5.0, 20.0, 25.0]) * KILO * MOL / METER.powi(3);
+        let viscosity = SIArray1::from_vec(
+            (0..5)
+                .map(|i| {
+                    State::new_nvt(&eos, temperature.get(i), MOL / density.get(i), &moles)
+                        .unwrap()
+                        .viscosity()
+                        .unwrap()
+                })
+                .collect(),
+        );
+        let data = TransportData::new_density(
+            TransportProperty::Viscosity,
+            temperature,
+            density,
+            viscosity,
+        )
+        .unwrap();
+        let result = fit_entropy_scaling(&record, &data, PetsOptions::default()).unwrap();
+        assert!(result.max_deviation < 1e-8);
+    }
+
+    #[test]
+    fn insufficient_data() {
+        let record = argon_parameters().pure_records[0].clone();
+        let data = TransportData::new(
+            TransportProperty::Diffusion,
+            arr1(&[300.0]) * KELVIN,
+            arr1(&[1.0]) * BAR,
+            arr1(&[1e-5]) * METER.powi(2) / SECOND,
+        )
+        .unwrap();
+        assert!(fit_entropy_scaling(&record, &data, PetsOptions::default()).is_err());
+
+        // wrong units
+        let data = TransportData::new(
+            TransportProperty::Viscosity,
+            arr1(&[300.0; 4]) * KELVIN,
+            arr1(&[1.0, 2.0, 3.0, 4.0]) * BAR,
+            arr1(&[1e-5; 4]) * METER.powi(2) / SECOND,
+        )
+        .unwrap();
+        assert!(fit_entropy_scaling(&record, &data, PetsOptions::default()).is_err());
+    }
+}
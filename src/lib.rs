@@ -5,11 +5,18 @@
 
 mod dft;
 mod eos;
+pub mod fitting;
 mod parameters;
+pub mod reduced;
 
 pub use dft::PetsFunctional;
-pub use eos::{Pets, PetsOptions};
-pub use parameters::{PetsParameters, PetsRecord};
+pub use eos::{
+    HardSphereDiameter, HardSphereModel, IdealGasModel, LennardJones, Pets, PetsOptions,
+};
+pub use parameters::{
+    CombiningRule, DipprRecord, NasaRecord, PetsBinaryRecord, PetsIdealGasRecord, PetsParameters,
+    PetsRecord, TransportProperty,
+};
 
 #[cfg(feature = "python")]
 pub mod python;
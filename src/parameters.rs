@@ -1,6 +1,9 @@
+use crate::eos::mie::LJ_EXPONENT;
 use feos_core::joback::JobackRecord;
-use feos_core::parameter::{Parameter, PureRecord};
+use feos_core::parameter::{Identifier, Parameter, ParameterError, PureRecord};
+use feos_core::EosError;
 use ndarray::{Array, Array1, Array2};
+use num_dual::DualNum;
 use num_traits::Zero;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -13,6 +16,30 @@ pub struct PetsRecord {
     pub sigma: f64,
     /// Energetic parameter in units of Kelvin
     pub epsilon_k: f64,
+    /// Segment number (1 if not given)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub m: Option<f64>,
+    /// Repulsive exponent of the Mie n-6 potential (12 if not given)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lambda_r: Option<f64>,
+    /// Dipole moment in units of Debye
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mu: Option<f64>,
+    /// Quadrupole moment in units of Debye Angstrom
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub q: Option<f64>,
+    /// Association volume parameter
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kappa_ab: Option<f64>,
+    /// Association energy parameter in units of Kelvin
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub epsilon_k_ab: Option<f64>,
+    /// Number of association sites of type A
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub na: Option<f64>,
+    /// Number of association sites of type B
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nb: Option<f64>,
     /// Entropy scaling parameters for viscosity
     #[serde(skip_serializing_if = "Option::is_none")]
     pub viscosity: Option<[f64; 4]>,
@@ -28,6 +55,30 @@ impl std::fmt::Display for PetsRecord {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "PetsRecord(sigma={}", self.sigma)?;
         write!(f, ", epsilon_k={}", self.epsilon_k)?;
+        if let Some(n) = &self.m {
+            write!(f, ", m={}", n)?;
+        }
+        if let Some(n) = &self.lambda_r {
+            write!(f, ", lambda_r={}", n)?;
+        }
+        if let Some(n) = &self.mu {
+            write!(f, ", mu={}", n)?;
+        }
+        if let Some(n) = &self.q {
+            write!(f, ", q={}", n)?;
+        }
+        if let Some(n) = &self.kappa_ab {
+            write!(f, ", kappa_ab={}", n)?;
+        }
+        if let Some(n) = &self.epsilon_k_ab {
+            write!(f, ", epsilon_k_ab={}", n)?;
+        }
+        if let Some(n) = &self.na {
+            write!(f, ", na={}", n)?;
+        }
+        if let Some(n) = &self.nb {
+            write!(f, ", nb={}", n)?;
+        }
         if let Some(n) = &self.viscosity {
             write!(f, ", viscosity={:?}", n)?;
         }
@@ -52,44 +103,296 @@ impl PetsRecord {
         PetsRecord {
             sigma,
             epsilon_k,
+            m: None,
+            lambda_r: None,
+            mu: None,
+            q: None,
+            kappa_ab: None,
+            epsilon_k_ab: None,
+            na: None,
+            nb: None,
             viscosity,
             diffusion,
             thermal_conductivity,
         }
     }
+
+    /// Return the record with the given segment number.
+    pub fn with_segments(mut self, m: f64) -> Self {
+        self.m = Some(m);
+        self
+    }
+
+    /// Return the record with the given repulsive exponent of the Mie potential.
+    pub fn with_repulsive_exponent(mut self, lambda_r: f64) -> Self {
+        self.lambda_r = Some(lambda_r);
+        self
+    }
+
+    /// Return the record with the given dipole moment in units of Debye.
+    pub fn with_dipole_moment(mut self, mu: f64) -> Self {
+        self.mu = Some(mu);
+        self
+    }
+
+    /// Return the record with the given quadrupole moment in units of Debye Angstrom.
+    pub fn with_quadrupole_moment(mut self, q: f64) -> Self {
+        self.q = Some(q);
+        self
+    }
+
+    /// Return the record with the given association parameters and
+    /// numbers of association sites.
+    pub fn with_association(mut self, kappa_ab: f64, epsilon_k_ab: f64, na: f64, nb: f64) -> Self {
+        self.kappa_ab = Some(kappa_ab);
+        self.epsilon_k_ab = Some(epsilon_k_ab);
+        self.na = Some(na);
+        self.nb = Some(nb);
+        self
+    }
+}
+
+/// Transport properties that are calculated using entropy scaling.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "python", pyo3::pyclass)]
+pub enum TransportProperty {
+    Viscosity,
+    /// Self-diffusion coefficient
+    Diffusion,
+    ThermalConductivity,
+}
+
+impl std::fmt::Display for TransportProperty {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Viscosity => write!(f, "viscosity"),
+            Self::Diffusion => write!(f, "diffusion"),
+            Self::ThermalConductivity => write!(f, "thermal conductivity"),
+        }
+    }
 }
 
+/// PeTS binary interaction parameters.
+///
+/// The binary interaction parameter is evaluated as
+/// `k_ij(T) = k_ij + k_ij_t / T`.
+///
+/// In JSON, the combining rule of a pair can be given as
+/// `"combining_rule": "Kong"`. Pairs without a combining rule use the
+/// combining rule of the parameters.
 #[derive(Serialize, Deserialize, Clone, Default, Debug)]
 pub struct PetsBinaryRecord {
-    k_ij: f64,
+    /// Binary dispersion energy interaction parameter
+    #[serde(default)]
+    pub k_ij: f64,
+    /// Temperature dependence of the binary interaction parameter in units of Kelvin
+    #[serde(default, skip_serializing_if = "Zero::is_zero")]
+    pub k_ij_t: f64,
+    /// Binary segment diameter interaction parameter
+    #[serde(default, skip_serializing_if = "Zero::is_zero")]
+    pub l_ij: f64,
+    /// Combining rule for the cross parameters of the pair
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub combining_rule: Option<CombiningRule>,
+}
+
+impl PetsBinaryRecord {
+    pub fn new(k_ij: f64, k_ij_t: f64, l_ij: f64) -> Self {
+        Self {
+            k_ij,
+            k_ij_t,
+            l_ij,
+            combining_rule: None,
+        }
+    }
+
+    /// Return the binary record with the given combining rule.
+    pub fn with_combining_rule(mut self, combining_rule: CombiningRule) -> Self {
+        self.combining_rule = Some(combining_rule);
+        self
+    }
 }
 
 impl From<f64> for PetsBinaryRecord {
     fn from(k_ij: f64) -> Self {
-        Self { k_ij }
+        Self::new(k_ij, 0.0, 0.0)
+    }
+}
+
+/// Coefficients of the NASA 7-coefficient polynomials for the ideal gas
+/// heat capacity `c_p / R = a1 + a2 T + a3 T² + a4 T³ + a5 T⁴`.
+///
+/// `a6` and `a7` are the integration constants of the enthalpy and the entropy
+/// (at 1 bar). The coefficients of `low` are used below `t_mid`, those of `high`
+/// above.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct NasaRecord {
+    /// Temperature in units of Kelvin that separates the two ranges
+    pub t_mid: f64,
+    /// Coefficients `a1` to `a7` below `t_mid`
+    pub low: [f64; 7],
+    /// Coefficients `a1` to `a7` above `t_mid`
+    pub high: [f64; 7],
+}
+
+impl NasaRecord {
+    pub fn new(t_mid: f64, low: [f64; 7], high: [f64; 7]) -> Self {
+        Self { t_mid, low, high }
+    }
+}
+
+/// Coefficients of the DIPPR 107 (Aly-Lee) equation for the ideal gas heat capacity
+/// `c_p = a + b ((c / T) / sinh(c / T))² + d ((e / T) / cosh(e / T))²`
+/// in units of J/(mol K).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DipprRecord {
+    pub a: f64,
+    pub b: f64,
+    /// Characteristic temperature in units of Kelvin
+    pub c: f64,
+    pub d: f64,
+    /// Characteristic temperature in units of Kelvin
+    pub e: f64,
+}
+
+impl DipprRecord {
+    pub fn new(a: f64, b: f64, c: f64, d: f64, e: f64) -> Self {
+        Self { a, b, c, d, e }
+    }
+}
+
+/// Ideal gas heat capacity correlation of a pure component.
+///
+/// In JSON, Joback records are given by their coefficients directly, NASA and
+/// DIPPR 107 records as `{"nasa": {...}}` and `{"dippr": {...}}`, respectively.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum PetsIdealGasRecord {
+    Joback(JobackRecord),
+    Nasa { nasa: NasaRecord },
+    Dippr { dippr: DipprRecord },
+}
+
+impl Default for PetsIdealGasRecord {
+    fn default() -> Self {
+        Self::Joback(JobackRecord::default())
+    }
+}
+
+impl From<JobackRecord> for PetsIdealGasRecord {
+    fn from(joback: JobackRecord) -> Self {
+        Self::Joback(joback)
+    }
+}
+
+impl From<NasaRecord> for PetsIdealGasRecord {
+    fn from(nasa: NasaRecord) -> Self {
+        Self::Nasa { nasa }
+    }
+}
+
+impl From<DipprRecord> for PetsIdealGasRecord {
+    fn from(dippr: DipprRecord) -> Self {
+        Self::Dippr { dippr }
+    }
+}
+
+impl std::fmt::Display for PetsIdealGasRecord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Joback(joback) => write!(f, "{}", joback),
+            Self::Nasa { nasa } => write!(
+                f,
+                "NasaRecord(t_mid={}, low={:?}, high={:?})",
+                nasa.t_mid, nasa.low, nasa.high
+            ),
+            Self::Dippr { dippr } => write!(
+                f,
+                "DipprRecord(a={}, b={}, c={}, d={}, e={})",
+                dippr.a, dippr.b, dippr.c, dippr.d, dippr.e
+            ),
+        }
+    }
+}
+
+/// Combining rules for the cross interaction parameters
+/// `sigma_ij` and `epsilon_k_ij`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "python", pyo3::pyclass)]
+pub enum CombiningRule {
+    /// Arithmetic mean of `sigma` and geometric mean of `epsilon_k`.
+    #[default]
+    LorentzBerthelot,
+    /// [Kong (1973)](https://doi.org/10.1063/1.1679903)
+    Kong,
+    /// [Waldman and Hagler (1993)](https://doi.org/10.1002/jcc.540140909)
+    WaldmanHagler,
+    /// Arithmetic mean of `sigma` and harmonic mean of `epsilon_k`
+    /// ([Fender and Halsey, 1962](https://doi.org/10.1063/1.1732447)).
+    FenderHalsey,
+}
+
+impl CombiningRule {
+    /// Cross parameters `(sigma_ij, epsilon_k_ij)` without binary corrections.
+    fn apply(&self, sigma: [f64; 2], epsilon_k: [f64; 2]) -> (f64, f64) {
+        let [s1, s2] = sigma;
+        let [e1, e2] = epsilon_k;
+        match self {
+            Self::LorentzBerthelot => (0.5 * (s1 + s2), (e1 * e2).sqrt()),
+            Self::Kong => {
+                let es6 = (e1 * s1.powi(6) * e2 * s2.powi(6)).sqrt();
+                let es12 = (0.5
+                    * ((e1 * s1.powi(12)).powf(1.0 / 13.0) + (e2 * s2.powi(12)).powf(1.0 / 13.0)))
+                .powi(13);
+                let s12 = (es12 / es6).powf(1.0 / 6.0);
+                (s12, es6 / s12.powi(6))
+            }
+            Self::WaldmanHagler => {
+                let s6 = 0.5 * (s1.powi(6) + s2.powi(6));
+                (
+                    s6.powf(1.0 / 6.0),
+                    (e1 * e2).sqrt() * (s1 * s2).powi(3) / s6,
+                )
+            }
+            Self::FenderHalsey => (0.5 * (s1 + s2), 2.0 * e1 * e2 / (e1 + e2)),
+        }
     }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct PetsParameters {
     pub molarweight: Array1<f64>,
+    pub m: Array1<f64>,
     pub sigma: Array1<f64>,
     pub epsilon_k: Array1<f64>,
+    pub lambda_r: Array1<f64>,
+    pub mu: Array1<f64>,
+    pub q: Array1<f64>,
+    pub kappa_ab: Array1<f64>,
+    pub epsilon_k_ab: Array1<f64>,
+    pub na: Array1<f64>,
+    pub nb: Array1<f64>,
     pub k_ij: Array2<f64>,
+    pub k_ij_t: Array2<f64>,
+    pub l_ij: Array2<f64>,
+    pub combining_rule: CombiningRule,
     pub sigma_ij: Array2<f64>,
+    /// Cross energy parameters `(1 - k_ij) * e_k_ij` without the
+    /// temperature dependence `k_ij_t`.
+    #[deprecated(note = "use `epsilon_k_ij(temperature)`, which includes `k_ij_t`")]
     pub epsilon_k_ij: Array2<f64>,
     pub e_k_ij: Array2<f64>,
     pub viscosity: Option<Array2<f64>>,
     pub diffusion: Option<Array2<f64>>,
     pub thermal_conductivity: Option<Array2<f64>>,
-    pub pure_records: Vec<PureRecord<PetsRecord, JobackRecord>>,
-    pub joback_records: Option<Vec<JobackRecord>>,
+    pub pure_records: Vec<PureRecord<PetsRecord, PetsIdealGasRecord>>,
+    pub ideal_gas_records: Option<Vec<PetsIdealGasRecord>>,
     pub binary_records: Array2<PetsBinaryRecord>,
 }
 
 impl Parameter for PetsParameters {
     type Pure = PetsRecord;
-    type IdealGas = JobackRecord;
+    type IdealGas = PetsIdealGasRecord;
     type Binary = PetsBinaryRecord;
 
     fn from_records(
@@ -99,8 +402,16 @@ impl Parameter for PetsParameters {
         let n = pure_records.len();
 
         let mut molarweight = Array::zeros(n);
+        let mut m = Array::zeros(n);
         let mut sigma = Array::zeros(n);
         let mut epsilon_k = Array::zeros(n);
+        let mut lambda_r = Array::zeros(n);
+        let mut mu = Array::zeros(n);
+        let mut q = Array::zeros(n);
+        let mut kappa_ab = Array::zeros(n);
+        let mut epsilon_k_ab = Array::zeros(n);
+        let mut na = Array::zeros(n);
+        let mut nb = Array::zeros(n);
         let mut viscosity = Vec::with_capacity(n);
         let mut diffusion = Vec::with_capacity(n);
         let mut thermal_conductivity = Vec::with_capacity(n);
@@ -110,8 +421,16 @@ impl Parameter for PetsParameters {
         for (i, record) in pure_records.iter().enumerate() {
             component_index.insert(record.identifier.clone(), i);
             let r = &record.model_record;
+            m[i] = r.m.unwrap_or(1.0);
             sigma[i] = r.sigma;
             epsilon_k[i] = r.epsilon_k;
+            lambda_r[i] = r.lambda_r.unwrap_or(LJ_EXPONENT);
+            mu[i] = r.mu.unwrap_or(0.0);
+            q[i] = r.q.unwrap_or(0.0);
+            kappa_ab[i] = r.kappa_ab.unwrap_or(0.0);
+            epsilon_k_ab[i] = r.epsilon_k_ab.unwrap_or(0.0);
+            na[i] = r.na.unwrap_or(1.0);
+            nb[i] = r.nb.unwrap_or(1.0);
             viscosity.push(r.viscosity);
             diffusion.push(r.diffusion);
             thermal_conductivity.push(r.thermal_conductivity);
@@ -119,58 +438,39 @@ impl Parameter for PetsParameters {
         }
 
         let k_ij = binary_records.map(|br| br.k_ij);
-        let mut epsilon_k_ij = Array::zeros((n, n));
-        let mut sigma_ij = Array::zeros((n, n));
-        let mut e_k_ij = Array::zeros((n, n));
-        for i in 0..n {
-            for j in 0..n {
-                e_k_ij[[i, j]] = (epsilon_k[i] * epsilon_k[j]).sqrt();
-                epsilon_k_ij[[i, j]] = (1.0 - k_ij[[i, j]]) * e_k_ij[[i, j]];
-                sigma_ij[[i, j]] = 0.5 * (sigma[i] + sigma[j]);
-            }
-        }
-
-        let viscosity_coefficients = if viscosity.iter().any(|v| v.is_none()) {
-            None
-        } else {
-            let mut v = Array2::zeros((4, viscosity.len()));
-            for (i, vi) in viscosity.iter().enumerate() {
-                v.column_mut(i).assign(&Array1::from(vi.unwrap().to_vec()));
-            }
-            Some(v)
-        };
-
-        let diffusion_coefficients = if diffusion.iter().any(|v| v.is_none()) {
-            None
-        } else {
-            let mut v = Array2::zeros((5, diffusion.len()));
-            for (i, vi) in diffusion.iter().enumerate() {
-                v.column_mut(i).assign(&Array1::from(vi.unwrap().to_vec()));
-            }
-            Some(v)
-        };
+        let k_ij_t = binary_records.map(|br| br.k_ij_t);
+        let l_ij = binary_records.map(|br| br.l_ij);
+        let combining_rule = CombiningRule::default();
+        let (sigma_ij, e_k_ij) =
+            cross_parameters(&sigma, &epsilon_k, &binary_records, combining_rule);
+        let epsilon_k_ij = (1.0 - &k_ij) * &e_k_ij;
 
-        let thermal_conductivity_coefficients = if thermal_conductivity.iter().any(|v| v.is_none())
-        {
-            None
-        } else {
-            let mut v = Array2::zeros((4, thermal_conductivity.len()));
-            for (i, vi) in thermal_conductivity.iter().enumerate() {
-                v.column_mut(i).assign(&Array1::from(vi.unwrap().to_vec()));
-            }
-            Some(v)
-        };
+        let viscosity_coefficients = entropy_scaling_coefficients(&viscosity);
+        let diffusion_coefficients = entropy_scaling_coefficients(&diffusion);
+        let thermal_conductivity_coefficients = entropy_scaling_coefficients(&thermal_conductivity);
 
-        let joback_records = pure_records
+        let ideal_gas_records = pure_records
             .iter()
             .map(|r| r.ideal_gas_record.clone())
             .collect();
 
+        #[allow(deprecated)]
         Self {
             molarweight,
+            m,
             sigma,
             epsilon_k,
+            lambda_r,
+            mu,
+            q,
+            kappa_ab,
+            epsilon_k_ab,
+            na,
+            nb,
             k_ij,
+            k_ij_t,
+            l_ij,
+            combining_rule,
             sigma_ij,
             epsilon_k_ij,
             e_k_ij,
@@ -178,7 +478,7 @@ impl Parameter for PetsParameters {
             diffusion: diffusion_coefficients,
             thermal_conductivity: thermal_conductivity_coefficients,
             pure_records,
-            joback_records,
+            ideal_gas_records,
             binary_records,
         }
     }
@@ -186,20 +486,163 @@ impl Parameter for PetsParameters {
     fn records(
         &self,
     ) -> (
-        &[PureRecord<PetsRecord, JobackRecord>],
+        &[PureRecord<PetsRecord, PetsIdealGasRecord>],
         &Array2<PetsBinaryRecord>,
     ) {
         (&self.pure_records, &self.binary_records)
     }
+
+    fn subset(&self, component_list: &[usize]) -> Self {
+        let pure_records = component_list
+            .iter()
+            .map(|&i| self.pure_records[i].clone())
+            .collect();
+        let n = component_list.len();
+        let binary_records = Array2::from_shape_fn([n, n], |(i, j)| {
+            self.binary_records[(component_list[i], component_list[j])].clone()
+        });
+
+        let mut parameters = Self::from_records(pure_records, binary_records);
+        parameters.combining_rule = self.combining_rule;
+        parameters.update_cross_parameters();
+        parameters
+    }
+}
+
+/// Cross parameters `sigma_ij` (including `l_ij`) and `e_k_ij` (excluding `k_ij`).
+///
+/// The combining rule of the binary record takes precedence over `combining_rule`.
+fn cross_parameters(
+    sigma: &Array1<f64>,
+    epsilon_k: &Array1<f64>,
+    binary_records: &Array2<PetsBinaryRecord>,
+    combining_rule: CombiningRule,
+) -> (Array2<f64>, Array2<f64>) {
+    let n = sigma.len();
+    let mut sigma_ij = Array::zeros((n, n));
+    let mut e_k_ij = Array::zeros((n, n));
+    for i in 0..n {
+        for j in 0..n {
+            let br = &binary_records[[i, j]];
+            let (s, e) = br
+                .combining_rule
+                .unwrap_or(combining_rule)
+                .apply([sigma[i], sigma[j]], [epsilon_k[i], epsilon_k[j]]);
+            sigma_ij[[i, j]] = (1.0 - br.l_ij) * s;
+            e_k_ij[[i, j]] = e;
+        }
+    }
+    (sigma_ij, e_k_ij)
+}
+
+/// Entropy scaling coefficients (one column per component),
+/// `None` if any component has no coefficients.
+fn entropy_scaling_coefficients<const N: usize>(
+    coefficients: &[Option<[f64; N]>],
+) -> Option<Array2<f64>> {
+    let n = coefficients.len();
+    let coefficients: Option<Vec<_>> = coefficients.iter().copied().collect();
+    coefficients.map(|c| Array2::from_shape_fn((N, n), |(j, i)| c[i][j]))
 }
 
 impl PetsParameters {
+    /// Parameters of a Lennard-Jones unit fluid with `sigma` = 1 A,
+    /// `epsilon_k` = 1 K and a molar weight of 1 g/mol.
+    pub fn unit_fluid() -> Self {
+        let identifier = Identifier::new("", Some("unit fluid"), None, None, None, None);
+        let model_record = PetsRecord::new(1.0, 1.0, None, None, None);
+        Self::new_pure(PureRecord::new(identifier, 1.0, model_record, None))
+    }
+
+    /// Return the parameters with the cross interaction parameters
+    /// calculated from the given combining rule.
+    ///
+    /// The combining rule is applied to all pairs and replaces the
+    /// combining rules of the binary records.
+    pub fn with_combining_rule(mut self, combining_rule: CombiningRule) -> Self {
+        self.binary_records
+            .iter_mut()
+            .for_each(|br| br.combining_rule = None);
+        self.combining_rule = combining_rule;
+        self.update_cross_parameters();
+        self
+    }
+
+    fn update_cross_parameters(&mut self) {
+        let (sigma_ij, e_k_ij) = cross_parameters(
+            &self.sigma,
+            &self.epsilon_k,
+            &self.binary_records,
+            self.combining_rule,
+        );
+        self.sigma_ij = sigma_ij;
+        #[allow(deprecated)]
+        {
+            self.epsilon_k_ij = (1.0 - &self.k_ij) * &e_k_ij;
+        }
+        self.e_k_ij = e_k_ij;
+    }
+
+    /// Error for missing entropy scaling coefficients of the given property.
+    ///
+    /// The error names the first component without coefficients.
+    pub(crate) fn missing_coefficients(&self, property: TransportProperty) -> EosError {
+        let missing = self.pure_records.iter().enumerate().find(|(_, r)| {
+            let m = &r.model_record;
+            match property {
+                TransportProperty::Viscosity => m.viscosity.is_none(),
+                TransportProperty::Diffusion => m.diffusion.is_none(),
+                TransportProperty::ThermalConductivity => m.thermal_conductivity.is_none(),
+            }
+        });
+        let component = match missing {
+            Some((i, r)) => r
+                .identifier
+                .name
+                .clone()
+                .unwrap_or_else(|| format!("Component {}", i + 1)),
+            None => String::from("unknown component"),
+        };
+        EosError::ParameterError(ParameterError::IncompatibleParameters(format!(
+            "missing {} coefficients for {}.",
+            property, component
+        )))
+    }
+
+    /// Whether any component consists of more than one segment.
+    pub fn has_chains(&self) -> bool {
+        self.m.iter().any(|&m| m != 1.0)
+    }
+
+    /// Whether any component has a dipole moment.
+    pub fn has_dipoles(&self) -> bool {
+        self.mu.iter().any(|&mu| mu != 0.0)
+    }
+
+    /// Whether any component has a quadrupole moment.
+    pub fn has_quadrupoles(&self) -> bool {
+        self.q.iter().any(|&q| q != 0.0)
+    }
+
+    /// Whether any component has association sites.
+    pub fn has_association(&self) -> bool {
+        self.kappa_ab.iter().any(|&k| k != 0.0)
+    }
+
+    /// Cross energy parameters `(1 - k_ij(T)) * e_k_ij` in units of Kelvin.
+    pub fn epsilon_k_ij<D: DualNum<f64>>(&self, temperature: D) -> Array2<D> {
+        let t_inv = temperature.recip();
+        Array2::from_shape_fn(self.e_k_ij.raw_dim(), |(i, j)| {
+            -(t_inv * self.k_ij_t[[i, j]] + self.k_ij[[i, j]] - 1.0) * self.e_k_ij[[i, j]]
+        })
+    }
+
     pub fn to_markdown(&self) -> String {
         let mut output = String::new();
         let o = &mut output;
         write!(
             o,
-            "|component|molarweight|$\\sigma$|$\\varepsilon$|\n|-|-|-|-|"
+            "|component|molarweight|$m$|$\\sigma$|$\\varepsilon$|\n|-|-|-|-|-|"
         )
         .unwrap();
         for i in 0..self.sigma.len() {
@@ -207,8 +650,8 @@ impl PetsParameters {
             let component = component.unwrap_or(format!("Component {}", i + 1));
             write!(
                 o,
-                "\n|{}|{}|{}|{}|",
-                component, self.molarweight[i], self.sigma[i], self.epsilon_k[i],
+                "\n|{}|{}|{}|{}|{}|",
+                component, self.molarweight[i], self.m[i], self.sigma[i], self.epsilon_k[i],
             )
             .unwrap();
         }
@@ -221,11 +664,38 @@ impl std::fmt::Display for PetsParameters {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "PetsParameters(")?;
         write!(f, "\n\tmolarweight={}", self.molarweight)?;
+        if self.has_chains() {
+            write!(f, "\n\tm={}", self.m)?;
+        }
         write!(f, "\n\tsigma={}", self.sigma)?;
         write!(f, "\n\tepsilon_k={}", self.epsilon_k)?;
+        if self.has_mie() {
+            write!(f, "\n\tlambda_r={}", self.lambda_r)?;
+        }
+        if self.has_dipoles() {
+            write!(f, "\n\tmu={}", self.mu)?;
+        }
+        if self.has_quadrupoles() {
+            write!(f, "\n\tq={}", self.q)?;
+        }
+        if self.has_association() {
+            write!(f, "\n\tkappa_ab={}", self.kappa_ab)?;
+            write!(f, "\n\tepsilon_k_ab={}", self.epsilon_k_ab)?;
+            write!(f, "\n\tna={}", self.na)?;
+            write!(f, "\n\tnb={}", self.nb)?;
+        }
         if !self.k_ij.iter().all(|k| k.is_zero()) {
             write!(f, "\n\tk_ij=\n{}", self.k_ij)?;
         }
+        if !self.k_ij_t.iter().all(|k| k.is_zero()) {
+            write!(f, "\n\tk_ij_t=\n{}", self.k_ij_t)?;
+        }
+        if !self.l_ij.iter().all(|l| l.is_zero()) {
+            write!(f, "\n\tl_ij=\n{}", self.l_ij)?;
+        }
+        if self.combining_rule != CombiningRule::default() {
+            write!(f, "\n\tcombining_rule={:?}", self.combining_rule)?;
+        }
         write!(f, "\n)")
     }
 }
@@ -233,7 +703,6 @@ impl std::fmt::Display for PetsParameters {
 #[cfg(test)]
 pub mod utils {
     use super::*;
-    use feos_core::joback::JobackRecord;
     use std::rc::Rc;
 
     pub fn argon_parameters() -> Rc<PetsParameters> {
@@ -256,7 +725,7 @@ pub mod utils {
                 },
                 "molarweight": 39.948
             }"#;
-        let argon_record: PureRecord<PetsRecord, JobackRecord> =
+        let argon_record: PureRecord<PetsRecord, PetsIdealGasRecord> =
             serde_json::from_str(argon_json).expect("Unable to parse json.");
         Rc::new(PetsParameters::new_pure(argon_record))
     }
@@ -278,7 +747,7 @@ pub mod utils {
                 },
                 "molarweight": 83.798
             }"#;
-        let krypton_record: PureRecord<PetsRecord, JobackRecord> =
+        let krypton_record: PureRecord<PetsRecord, PetsIdealGasRecord> =
             serde_json::from_str(krypton_json).expect("Unable to parse json.");
         Rc::new(PetsParameters::new_pure(krypton_record))
     }
@@ -322,8 +791,23 @@ pub mod utils {
                 "molarweight": 83.798
             }
         ]"#;
-        let binary_record: Vec<PureRecord<PetsRecord, JobackRecord>> =
+        let binary_record: Vec<PureRecord<PetsRecord, PetsIdealGasRecord>> =
             serde_json::from_str(binary_json).expect("Unable to parse json.");
         Rc::new(PetsParameters::new_binary(binary_record, None))
     }
+
+    /// Parameters with modified pure component records (e.g. segment numbers).
+    pub fn map_model_records<F: Fn(usize, PetsRecord) -> PetsRecord>(
+        parameters: &PetsParameters,
+        f: F,
+    ) -> Rc<PetsParameters> {
+        let mut pure_records = parameters.pure_records.clone();
+        for (i, r) in pure_records.iter_mut().enumerate() {
+            r.model_record = f(i, r.model_record.clone());
+        }
+        Rc::new(PetsParameters::from_records(
+            pure_records,
+            parameters.binary_records.clone(),
+        ))
+    }
 }
@@ -1,20 +1,23 @@
+use crate::eos::{HardSphereDiameter, HardSphereModel, IdealGasModel, PetsOptions};
+use crate::fitting::*;
 use crate::parameters::*;
-use feos_core::joback::JobackRecord;
+use crate::reduced::ReducedUnits;
 use feos_core::parameter::*;
 use feos_core::python::joback::PyJobackRecord;
 use feos_core::python::parameter::*;
 use feos_core::{impl_json_handling, impl_parameter, impl_pure_record};
 use ndarray::Array2;
-use numpy::{PyArray2, ToPyArray};
+use numpy::{PyArray1, PyArray2, ToPyArray};
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
+use quantity::python::{PySIArray1, PySINumber};
 use std::convert::TryFrom;
 use std::rc::Rc;
 
 /// Create a set of PeTS parameters from records.
 #[pyclass(name = "PetsRecord", unsendable)]
 #[pyo3(
-    text_signature = "(sigma, epsilon_k, viscosity=None, diffusion=None, thermal_conductivity=None)"
+    text_signature = "(sigma, epsilon_k, viscosity=None, diffusion=None, thermal_conductivity=None, m=None, mu=None, q=None, kappa_ab=None, epsilon_k_ab=None, na=None, nb=None, lambda_r=None)"
 )]
 #[derive(Clone)]
 pub struct PyPetsRecord(PetsRecord);
@@ -28,14 +31,66 @@ impl PyPetsRecord {
         viscosity: Option<[f64; 4]>,
         diffusion: Option<[f64; 5]>,
         thermal_conductivity: Option<[f64; 4]>,
+        m: Option<f64>,
+        mu: Option<f64>,
+        q: Option<f64>,
+        kappa_ab: Option<f64>,
+        epsilon_k_ab: Option<f64>,
+        na: Option<f64>,
+        nb: Option<f64>,
+        lambda_r: Option<f64>,
     ) -> Self {
-        Self(PetsRecord::new(
-            sigma,
-            epsilon_k,
-            viscosity,
-            diffusion,
-            thermal_conductivity,
-        ))
+        let mut record =
+            PetsRecord::new(sigma, epsilon_k, viscosity, diffusion, thermal_conductivity);
+        record.m = m;
+        record.mu = mu;
+        record.q = q;
+        record.kappa_ab = kappa_ab;
+        record.epsilon_k_ab = epsilon_k_ab;
+        record.na = na;
+        record.nb = nb;
+        record.lambda_r = lambda_r;
+        Self(record)
+    }
+
+    #[getter]
+    fn get_m(&self) -> Option<f64> {
+        self.0.m
+    }
+
+    #[getter]
+    fn get_mu(&self) -> Option<f64> {
+        self.0.mu
+    }
+
+    #[getter]
+    fn get_q(&self) -> Option<f64> {
+        self.0.q
+    }
+
+    #[getter]
+    fn get_kappa_ab(&self) -> Option<f64> {
+        self.0.kappa_ab
+    }
+
+    #[getter]
+    fn get_epsilon_k_ab(&self) -> Option<f64> {
+        self.0.epsilon_k_ab
+    }
+
+    #[getter]
+    fn get_na(&self) -> Option<f64> {
+        self.0.na
+    }
+
+    #[getter]
+    fn get_nb(&self) -> Option<f64> {
+        self.0.nb
+    }
+
+    #[getter]
+    fn get_lambda_r(&self) -> Option<f64> {
+        self.0.lambda_r
     }
 
     #[getter]
@@ -69,7 +124,89 @@ impl PyPetsRecord {
 }
 
 impl_json_handling!(PyPetsRecord);
-impl_pure_record!(PetsRecord, PyPetsRecord, JobackRecord, PyJobackRecord);
+
+/// Ideal gas heat capacity correlation of a pure component.
+///
+/// Use the static methods `joback`, `nasa` or `dippr` to create a record.
+#[pyclass(name = "IdealGasRecord", unsendable)]
+#[derive(Clone)]
+pub struct PyPetsIdealGasRecord(PetsIdealGasRecord);
+
+#[pymethods]
+impl PyPetsIdealGasRecord {
+    /// Ideal gas record from Joback parameters.
+    ///
+    /// Parameters
+    /// ----------
+    /// record : JobackRecord
+    ///     The Joback parameters.
+    ///
+    /// Returns
+    /// -------
+    /// IdealGasRecord
+    #[pyo3(text_signature = "(record)")]
+    #[staticmethod]
+    fn joback(record: PyJobackRecord) -> Self {
+        Self(record.0.into())
+    }
+
+    /// Ideal gas record from NASA 7-coefficient polynomials.
+    ///
+    /// Parameters
+    /// ----------
+    /// t_mid : float
+    ///     Temperature in units of Kelvin that separates the two ranges.
+    /// low : List[float]
+    ///     Coefficients a1 to a7 below `t_mid`.
+    /// high : List[float]
+    ///     Coefficients a1 to a7 above `t_mid`.
+    ///
+    /// Returns
+    /// -------
+    /// IdealGasRecord
+    #[pyo3(text_signature = "(t_mid, low, high)")]
+    #[staticmethod]
+    fn nasa(t_mid: f64, low: [f64; 7], high: [f64; 7]) -> Self {
+        Self(NasaRecord::new(t_mid, low, high).into())
+    }
+
+    /// Ideal gas record from the DIPPR 107 (Aly-Lee) equation
+    /// c_p = a + b ((c / T) / sinh(c / T))² + d ((e / T) / cosh(e / T))².
+    ///
+    /// Parameters
+    /// ----------
+    /// a : float
+    ///     Constant in units of J/(mol K).
+    /// b : float
+    ///     Coefficient in units of J/(mol K).
+    /// c : float
+    ///     Characteristic temperature in units of Kelvin.
+    /// d : float
+    ///     Coefficient in units of J/(mol K).
+    /// e : float
+    ///     Characteristic temperature in units of Kelvin.
+    ///
+    /// Returns
+    /// -------
+    /// IdealGasRecord
+    #[pyo3(text_signature = "(a, b, c, d, e)")]
+    #[staticmethod]
+    fn dippr(a: f64, b: f64, c: f64, d: f64, e: f64) -> Self {
+        Self(DipprRecord::new(a, b, c, d, e).into())
+    }
+
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(self.0.to_string())
+    }
+}
+
+impl_json_handling!(PyPetsIdealGasRecord);
+impl_pure_record!(
+    PetsRecord,
+    PyPetsRecord,
+    PetsIdealGasRecord,
+    PyPetsIdealGasRecord
+);
 
 /// Create a set of PeTS parameters from records.
 ///
@@ -112,11 +249,32 @@ impl PyPetsParameters {
     ///     entropy scaling parameters for self-diffusion.
     /// thermal_conductivity: List[List[float]], optional
     ///     entropy scaling parameters for thermal conductivity.
+    /// k_ij_t: numpy.ndarray[float], optional
+    ///     matrix of the temperature dependence of the binary interaction
+    ///     parameters in units of Kelvin, k_ij(T) = k_ij + k_ij_t / T.
+    /// l_ij: numpy.ndarray[float], optional
+    ///     matrix of binary segment diameter interaction parameters.
+    /// m: List[float], optional
+    ///     segment numbers. Defaults to 1 for all components.
+    /// mu: List[float], optional
+    ///     dipole moments in units of Debye. Defaults to 0 for all components.
+    /// q: List[float], optional
+    ///     quadrupole moments in units of Debye Angstrom. Defaults to 0 for all components.
+    /// kappa_ab: List[float], optional
+    ///     association volume parameters. Defaults to 0 (no association) for all components.
+    /// epsilon_k_ab: List[float], optional
+    ///     association energy parameters in units of Kelvin.
+    /// na: List[float], optional
+    ///     numbers of association sites of type A. Defaults to 1 for all components.
+    /// nb: List[float], optional
+    ///     numbers of association sites of type B. Defaults to 1 for all components.
+    /// lambda_r: List[float], optional
+    ///     repulsive exponents of the Mie potentials. Defaults to 12 for all components.
     /// Returns
     /// -------
     /// PetsParameters
     #[pyo3(
-        text_signature = "(sigma, epsilon_k, k_ij=None, molarweight=None, viscosity=None, diffusion=None, thermal_conductivity=None)"
+        text_signature = "(sigma, epsilon_k, k_ij=None, molarweight=None, viscosity=None, diffusion=None, thermal_conductivity=None, k_ij_t=None, l_ij=None, m=None, mu=None, q=None, kappa_ab=None, epsilon_k_ab=None, na=None, nb=None, lambda_r=None)"
     )]
     #[staticmethod]
     fn from_lists(
@@ -127,6 +285,16 @@ impl PyPetsParameters {
         viscosity: Option<Vec<[f64; 4]>>,
         diffusion: Option<Vec<[f64; 5]>>,
         thermal_conductivity: Option<Vec<[f64; 4]>>,
+        k_ij_t: Option<&PyArray2<f64>>,
+        l_ij: Option<&PyArray2<f64>>,
+        m: Option<Vec<f64>>,
+        mu: Option<Vec<f64>>,
+        q: Option<Vec<f64>>,
+        kappa_ab: Option<Vec<f64>>,
+        epsilon_k_ab: Option<Vec<f64>>,
+        na: Option<Vec<f64>>,
+        nb: Option<Vec<f64>>,
+        lambda_r: Option<Vec<f64>>,
     ) -> PyResult<Self> {
         // Check if all inputs have the same length
         let n = sigma.len();
@@ -135,7 +303,19 @@ impl PyPetsParameters {
             Some(epsilon_k.len()),
             k_ij.as_ref().map_or(None, |v| Some(v.shape()[0])),
             k_ij.as_ref().map_or(None, |v| Some(v.shape()[1])),
+            k_ij_t.as_ref().map_or(None, |v| Some(v.shape()[0])),
+            k_ij_t.as_ref().map_or(None, |v| Some(v.shape()[1])),
+            l_ij.as_ref().map_or(None, |v| Some(v.shape()[0])),
+            l_ij.as_ref().map_or(None, |v| Some(v.shape()[1])),
             molarweight.as_ref().map_or(None, |v| Some(v.len())),
+            m.as_ref().map_or(None, |v| Some(v.len())),
+            mu.as_ref().map_or(None, |v| Some(v.len())),
+            q.as_ref().map_or(None, |v| Some(v.len())),
+            kappa_ab.as_ref().map_or(None, |v| Some(v.len())),
+            epsilon_k_ab.as_ref().map_or(None, |v| Some(v.len())),
+            na.as_ref().map_or(None, |v| Some(v.len())),
+            nb.as_ref().map_or(None, |v| Some(v.len())),
+            lambda_r.as_ref().map_or(None, |v| Some(v.len())),
             viscosity.as_ref().map_or(None, |v| Some(v.len())),
             diffusion.as_ref().map_or(None, |v| Some(v.len())),
             thermal_conductivity
@@ -157,13 +337,21 @@ impl PyPetsParameters {
             .map(|i| {
                 let identifier =
                     Identifier::new(format!("{}", i).as_str(), None, None, None, None, None);
-                let model_record = PetsRecord::new(
+                let mut model_record = PetsRecord::new(
                     sigma[i],
                     epsilon_k[i],
                     viscosity.as_ref().map_or(None, |v| Some(v[i])),
                     diffusion.as_ref().map_or(None, |v| Some(v[i])),
                     thermal_conductivity.as_ref().map_or(None, |v| Some(v[i])),
                 );
+                model_record.m = m.as_ref().map(|v| v[i]);
+                model_record.mu = mu.as_ref().map(|v| v[i]);
+                model_record.q = q.as_ref().map(|v| v[i]);
+                model_record.kappa_ab = kappa_ab.as_ref().map(|v| v[i]);
+                model_record.epsilon_k_ab = epsilon_k_ab.as_ref().map(|v| v[i]);
+                model_record.na = na.as_ref().map(|v| v[i]);
+                model_record.nb = nb.as_ref().map(|v| v[i]);
+                model_record.lambda_r = lambda_r.as_ref().map(|v| v[i]);
                 PureRecord::new(
                     identifier,
                     molarweight.as_ref().map_or(1.0, |v| v[i]),
@@ -174,10 +362,12 @@ impl PyPetsParameters {
             })
             .collect();
 
-        let binary = match k_ij {
-            Some(v) => v.to_owned_array().mapv(f64::into),
-            None => Array2::from_shape_fn((n, n), |(_, _)| PetsBinaryRecord::from(0.0)),
-        };
+        let k_ij = k_ij.map_or_else(|| Array2::zeros((n, n)), |v| v.to_owned_array());
+        let k_ij_t = k_ij_t.map_or_else(|| Array2::zeros((n, n)), |v| v.to_owned_array());
+        let l_ij = l_ij.map_or_else(|| Array2::zeros((n, n)), |v| v.to_owned_array());
+        let binary = Array2::from_shape_fn((n, n), |(i, j)| {
+            PetsBinaryRecord::new(k_ij[[i, j]], k_ij_t[[i, j]], l_ij[[i, j]])
+        });
 
         Ok(Self(Rc::new(PetsParameters::from_records(
             pure_records,
@@ -201,11 +391,27 @@ impl PyPetsParameters {
     ///     entropy scaling parameters for self-diffusion.
     /// thermal_conductivity: List[float], optional
     ///     entropy scaling parameters for thermal conductivity.
+    /// m: float, optional
+    ///     segment number. Defaults to 1.
+    /// mu: float, optional
+    ///     dipole moment in units of Debye. Defaults to 0.
+    /// q: float, optional
+    ///     quadrupole moment in units of Debye Angstrom. Defaults to 0.
+    /// kappa_ab: float, optional
+    ///     association volume parameter. Defaults to 0 (no association).
+    /// epsilon_k_ab: float, optional
+    ///     association energy parameter in units of Kelvin.
+    /// na: float, optional
+    ///     number of association sites of type A. Defaults to 1.
+    /// nb: float, optional
+    ///     number of association sites of type B. Defaults to 1.
+    /// lambda_r: float, optional
+    ///     repulsive exponent of the Mie potential. Defaults to 12.
     /// Returns
     /// -------
     /// PetsParameters
     #[pyo3(
-        text_signature = "(sigma, epsilon_k, molarweight=None, viscosity=None, diffusion=None, thermal_conductivity=None)"
+        text_signature = "(sigma, epsilon_k, molarweight=None, viscosity=None, diffusion=None, thermal_conductivity=None, m=None, mu=None, q=None, kappa_ab=None, epsilon_k_ab=None, na=None, nb=None, lambda_r=None)"
     )]
     #[staticmethod]
     fn from_values(
@@ -215,22 +421,51 @@ impl PyPetsParameters {
         viscosity: Option<[f64; 4]>,
         diffusion: Option<[f64; 5]>,
         thermal_conductivity: Option<[f64; 4]>,
+        m: Option<f64>,
+        mu: Option<f64>,
+        q: Option<f64>,
+        kappa_ab: Option<f64>,
+        epsilon_k_ab: Option<f64>,
+        na: Option<f64>,
+        nb: Option<f64>,
+        lambda_r: Option<f64>,
     ) -> Self {
+        let mut model_record = PetsRecord::new(
+            sigma,
+            epsilon_k,
+            viscosity.map_or(None, |v| Some(v)),
+            diffusion.map_or(None, |v| Some(v)),
+            thermal_conductivity.map_or(None, |v| Some(v)),
+        );
+        model_record.m = m;
+        model_record.mu = mu;
+        model_record.q = q;
+        model_record.kappa_ab = kappa_ab;
+        model_record.epsilon_k_ab = epsilon_k_ab;
+        model_record.na = na;
+        model_record.nb = nb;
+        model_record.lambda_r = lambda_r;
         let pure_record = PureRecord::new(
             Identifier::new(format!("{}", 1).as_str(), None, None, None, None, None),
             molarweight.map_or(1.0, |v| v),
-            PetsRecord::new(
-                sigma,
-                epsilon_k,
-                viscosity.map_or(None, |v| Some(v)),
-                diffusion.map_or(None, |v| Some(v)),
-                thermal_conductivity.map_or(None, |v| Some(v)),
-            ),
+            model_record,
             None,
         );
         Self(Rc::new(PetsParameters::new_pure(pure_record)))
     }
 
+    /// Parameters of a Lennard-Jones unit fluid with sigma = 1 A,
+    /// epsilon_k = 1 K and a molar weight of 1 g/mol.
+    ///
+    /// Returns
+    /// -------
+    /// PetsParameters
+    #[pyo3(text_signature = "()")]
+    #[staticmethod]
+    fn unit_fluid() -> Self {
+        Self(Rc::new(PetsParameters::unit_fluid()))
+    }
+
     #[getter]
     fn get_pure_records(&self) -> Vec<PyPureRecord> {
         self.0
@@ -245,6 +480,39 @@ impl PyPetsParameters {
         self.0.k_ij.view().to_pyarray(py)
     }
 
+    #[getter]
+    fn get_k_ij_t<'py>(&self, py: Python<'py>) -> &'py PyArray2<f64> {
+        self.0.k_ij_t.view().to_pyarray(py)
+    }
+
+    #[getter]
+    fn get_l_ij<'py>(&self, py: Python<'py>) -> &'py PyArray2<f64> {
+        self.0.l_ij.view().to_pyarray(py)
+    }
+
+    #[getter]
+    fn get_combining_rule(&self) -> CombiningRule {
+        self.0.combining_rule
+    }
+
+    /// Return the parameters with the cross interaction parameters
+    /// calculated from the given combining rule.
+    ///
+    /// Parameters
+    /// ----------
+    /// combining_rule : CombiningRule
+    ///     The combining rule for sigma_ij and epsilon_k_ij.
+    ///
+    /// Returns
+    /// -------
+    /// PetsParameters
+    #[pyo3(text_signature = "($self, combining_rule)")]
+    fn with_combining_rule(&self, combining_rule: CombiningRule) -> Self {
+        Self(Rc::new(
+            (*self.0).clone().with_combining_rule(combining_rule),
+        ))
+    }
+
     fn _repr_markdown_(&self) -> String {
         self.0.to_markdown()
     }
@@ -255,3 +523,594 @@ impl PyPetsParameters {
 }
 
 impl_parameter!(PetsParameters, PyPetsParameters);
+
+/// Reference quantities of the reduced units of a pure component.
+///
+/// Parameters
+/// ----------
+/// parameters : PetsParameters
+///     The PeTS parameters.
+/// component : int, optional
+///     The index of the component. Defaults to 0.
+///
+/// Returns
+/// -------
+/// ReducedUnits
+#[pyclass(name = "ReducedUnits", unsendable)]
+#[pyo3(text_signature = "(parameters, component=0)")]
+#[derive(Clone)]
+pub struct PyReducedUnits(pub ReducedUnits);
+
+#[pymethods]
+impl PyReducedUnits {
+    #[new]
+    #[args(component = "0")]
+    fn new(parameters: PyPetsParameters, component: usize) -> Self {
+        Self(ReducedUnits::new(&parameters.0, component))
+    }
+
+    #[getter]
+    fn get_temperature(&self) -> PySINumber {
+        self.0.temperature().into()
+    }
+
+    #[getter]
+    fn get_density(&self) -> PySINumber {
+        self.0.density().into()
+    }
+
+    #[getter]
+    fn get_pressure(&self) -> PySINumber {
+        self.0.pressure().into()
+    }
+
+    #[getter]
+    fn get_molar_energy(&self) -> PySINumber {
+        self.0.molar_energy().into()
+    }
+
+    #[getter]
+    fn get_surface_tension(&self) -> PySINumber {
+        self.0.surface_tension().into()
+    }
+
+    #[getter]
+    fn get_time(&self) -> PySINumber {
+        self.0.time().into()
+    }
+
+    #[getter]
+    fn get_viscosity(&self) -> PySINumber {
+        self.0.viscosity().into()
+    }
+
+    #[getter]
+    fn get_diffusion(&self) -> PySINumber {
+        self.0.diffusion().into()
+    }
+
+    #[getter]
+    fn get_thermal_conductivity(&self) -> PySINumber {
+        self.0.thermal_conductivity().into()
+    }
+}
+
+/// Experimental vapor pressures of a pure substance.
+///
+/// Parameters
+/// ----------
+/// temperature : SIArray1
+///     Temperatures of the data points.
+/// pressure : SIArray1
+///     Vapor pressures of the data points.
+/// weight : float, optional
+///     Weight of the data set in the objective function. Defaults to 1.0.
+///
+/// Returns
+/// -------
+/// VaporPressure
+#[pyclass(name = "VaporPressure", unsendable)]
+#[pyo3(text_signature = "(temperature, pressure, weight=1.0)")]
+#[derive(Clone)]
+pub struct PyVaporPressure(pub VaporPressure);
+
+#[pymethods]
+impl PyVaporPressure {
+    #[new]
+    #[args(weight = "1.0")]
+    fn new(temperature: PySIArray1, pressure: PySIArray1, weight: f64) -> PyResult<Self> {
+        let mut data = VaporPressure::new(temperature.into(), pressure.into())?;
+        data.weight = weight;
+        Ok(Self(data))
+    }
+}
+
+/// Experimental liquid densities of a pure substance.
+///
+/// Parameters
+/// ----------
+/// temperature : SIArray1
+///     Temperatures of the data points.
+/// pressure : SIArray1
+///     Pressures of the data points.
+/// density : SIArray1
+///     Molar densities of the data points.
+/// weight : float, optional
+///     Weight of the data set in the objective function. Defaults to 1.0.
+///
+/// Returns
+/// -------
+/// LiquidDensity
+#[pyclass(name = "LiquidDensity", unsendable)]
+#[pyo3(text_signature = "(temperature, pressure, density, weight=1.0)")]
+#[derive(Clone)]
+pub struct PyLiquidDensity(pub LiquidDensity);
+
+#[pymethods]
+impl PyLiquidDensity {
+    #[new]
+    #[args(weight = "1.0")]
+    fn new(
+        temperature: PySIArray1,
+        pressure: PySIArray1,
+        density: PySIArray1,
+        weight: f64,
+    ) -> PyResult<Self> {
+        let mut data = LiquidDensity::new(temperature.into(), pressure.into(), density.into())?;
+        data.weight = weight;
+        Ok(Self(data))
+    }
+}
+
+/// Result of the regression of pure component parameters.
+#[pyclass(name = "PureFitResult", unsendable)]
+#[derive(Clone)]
+pub struct PyPureFitResult(pub PureFitResult);
+
+#[pymethods]
+impl PyPureFitResult {
+    #[getter]
+    fn get_record(&self) -> PyPetsRecord {
+        PyPetsRecord(self.0.record.clone())
+    }
+
+    #[getter]
+    fn get_aad_vapor_pressure(&self) -> f64 {
+        self.0.aad_vapor_pressure
+    }
+
+    #[getter]
+    fn get_aad_liquid_density(&self) -> f64 {
+        self.0.aad_liquid_density
+    }
+
+    #[getter]
+    fn get_failed_vapor_pressures(&self) -> Vec<usize> {
+        self.0.failed_vapor_pressures.clone()
+    }
+
+    #[getter]
+    fn get_failed_liquid_densities(&self) -> Vec<usize> {
+        self.0.failed_liquid_densities.clone()
+    }
+
+    #[getter]
+    fn get_iterations(&self) -> usize {
+        self.0.iterations
+    }
+}
+
+/// Fit the PeTS parameters sigma and epsilon_k of a pure substance
+/// to experimental vapor pressures and liquid densities.
+///
+/// Parameters
+/// ----------
+/// pure_record : PureRecord
+///     Pure substance record that contains the initial parameters.
+/// vapor_pressure : VaporPressure
+///     Experimental vapor pressures.
+/// liquid_density : LiquidDensity
+///     Experimental liquid densities.
+/// max_iter : int, optional
+///     Maximum number of iterations. Defaults to 500.
+/// tol : float, optional
+///     Tolerance of the optimizer. Defaults to 1e-10.
+/// feynman_hibbs : bool, optional
+///     Apply the Feynman-Hibbs quantum correction. Defaults to False.
+/// tail_correction : bool, optional
+///     Truncate the potential at 2.5 sigma without shifting and add the
+///     long-range correction. Defaults to False.
+/// hard_sphere_model : HardSphereModel, optional
+///     Hard-sphere mixture reference. Defaults to HardSphereModel.BMCSL.
+/// hs_diameter : HardSphereDiameter, optional
+///     Model of the hard-sphere diameter. Defaults to HardSphereDiameter.Correlation.
+///
+/// Returns
+/// -------
+/// PureFitResult
+#[pyfunction(
+    max_iter = "500",
+    tol = "1e-10",
+    feynman_hibbs = "false",
+    tail_correction = "false",
+    hard_sphere_model = "HardSphereModel::BMCSL",
+    hs_diameter = "HardSphereDiameter::Correlation"
+)]
+#[pyo3(
+    name = "fit_pure",
+    text_signature = "(pure_record, vapor_pressure, liquid_density, max_iter=500, tol=1e-10, feynman_hibbs=False, tail_correction=False, hard_sphere_model=HardSphereModel.BMCSL, hs_diameter=HardSphereDiameter.Correlation)"
+)]
+pub fn py_fit_pure(
+    pure_record: PyPureRecord,
+    vapor_pressure: PyVaporPressure,
+    liquid_density: PyLiquidDensity,
+    max_iter: usize,
+    tol: f64,
+    feynman_hibbs: bool,
+    tail_correction: bool,
+    hard_sphere_model: HardSphereModel,
+    hs_diameter: HardSphereDiameter,
+) -> PyResult<PyPureFitResult> {
+    let eos_options = PetsOptions {
+        feynman_hibbs,
+        tail_correction,
+        hard_sphere_model,
+        hs_diameter,
+        ..Default::default()
+    };
+    let options = FitOptions {
+        max_iter,
+        tol,
+        ..Default::default()
+    };
+    Ok(PyPureFitResult(fit_pure(
+        &pure_record.0,
+        &vapor_pressure.0,
+        &liquid_density.0,
+        eos_options,
+        options,
+    )?))
+}
+
+/// Experimental phase equilibria (Txy or pxy data) of a binary mixture.
+///
+/// Parameters
+/// ----------
+/// temperature : SIArray1
+///     Temperatures of the data points.
+/// pressure : SIArray1
+///     Pressures of the data points.
+/// liquid_molefracs : numpy.ndarray[float]
+///     Liquid mole fractions of the first component.
+/// vapor_molefracs : numpy.ndarray[float], optional
+///     Vapor mole fractions of the first component.
+/// weight : float, optional
+///     Weight of the data set in the objective function. Defaults to 1.0.
+/// isobaric : bool, optional
+///     Calculate the bubble points at the given pressures instead of
+///     the given temperatures. Defaults to False.
+///
+/// Returns
+/// -------
+/// BinaryVle
+#[pyclass(name = "BinaryVle", unsendable)]
+#[pyo3(
+    text_signature = "(temperature, pressure, liquid_molefracs, vapor_molefracs=None, weight=1.0, isobaric=False)"
+)]
+#[derive(Clone)]
+pub struct PyBinaryVle(pub BinaryVle);
+
+#[pymethods]
+impl PyBinaryVle {
+    #[new]
+    #[args(vapor_molefracs = "None", weight = "1.0", isobaric = "false")]
+    fn new(
+        temperature: PySIArray1,
+        pressure: PySIArray1,
+        liquid_molefracs: &PyArray1<f64>,
+        vapor_molefracs: Option<&PyArray1<f64>>,
+        weight: f64,
+        isobaric: bool,
+    ) -> PyResult<Self> {
+        let mut data = BinaryVle::new(
+            temperature.into(),
+            pressure.into(),
+            liquid_molefracs.to_owned_array(),
+            vapor_molefracs.map(|y| y.to_owned_array()),
+        )?;
+        data.weight = weight;
+        data.isobaric = isobaric;
+        Ok(Self(data))
+    }
+}
+
+/// Result of the regression of a binary interaction parameter.
+#[pyclass(name = "BinaryFitResult", unsendable)]
+#[derive(Clone)]
+pub struct PyBinaryFitResult(pub BinaryFitResult);
+
+#[pymethods]
+impl PyBinaryFitResult {
+    #[getter]
+    fn get_k_ij(&self) -> f64 {
+        self.0.record.k_ij
+    }
+
+    #[getter]
+    fn get_k_ij_t(&self) -> f64 {
+        self.0.record.k_ij_t
+    }
+
+    #[getter]
+    fn get_aad_pressure(&self) -> Option<f64> {
+        self.0.aad_pressure
+    }
+
+    #[getter]
+    fn get_aad_temperature(&self) -> Option<f64> {
+        self.0.aad_temperature
+    }
+
+    #[getter]
+    fn get_aad_vapor_molefracs(&self) -> Option<f64> {
+        self.0.aad_vapor_molefracs
+    }
+
+    #[getter]
+    fn get_failed_bubble_points(&self) -> Vec<Vec<usize>> {
+        self.0.failed_bubble_points.clone()
+    }
+
+    #[getter]
+    fn get_iterations(&self) -> usize {
+        self.0.iterations
+    }
+}
+
+/// Fit the binary interaction parameter k_ij of a binary mixture
+/// to experimental phase equilibria.
+///
+/// Parameters
+/// ----------
+/// pure_records : List[PureRecord]
+///     Pure substance records of both components.
+/// data : List[BinaryVle]
+///     Experimental phase equilibria.
+/// objective : BinaryVleObjective, optional
+///     Quantity used to measure the deviations. Defaults to the bubble point
+///     pressure (isothermal data) or temperature (isobaric data).
+/// k_ij : float, optional
+///     Initial value of the binary interaction parameter. Defaults to 0.0.
+/// k_ij_t : float, optional
+///     Initial value of the temperature dependence of the binary
+///     interaction parameter in units of Kelvin. Defaults to 0.0.
+/// temperature_dependent : bool, optional
+///     Whether k_ij_t is fitted as well. Defaults to False.
+/// l_ij : float, optional
+///     Binary segment diameter interaction parameter that is kept
+///     constant during the regression. Defaults to 0.0.
+/// combining_rule : CombiningRule, optional
+///     Combining rule of the pair. Defaults to the combining rule of the parameters.
+/// max_iter : int, optional
+///     Maximum number of iterations. Defaults to 500.
+/// tol : float, optional
+///     Tolerance of the optimizer. Defaults to 1e-10.
+/// feynman_hibbs : bool, optional
+///     Apply the Feynman-Hibbs quantum correction. Defaults to False.
+/// tail_correction : bool, optional
+///     Truncate the potential at 2.5 sigma without shifting and add the
+///     long-range correction. Defaults to False.
+/// hard_sphere_model : HardSphereModel, optional
+///     Hard-sphere mixture reference. Defaults to HardSphereModel.BMCSL.
+/// hs_diameter : HardSphereDiameter, optional
+///     Model of the hard-sphere diameter. Defaults to HardSphereDiameter.Correlation.
+///
+/// Returns
+/// -------
+/// BinaryFitResult
+#[pyfunction(
+    objective = "None",
+    k_ij = "0.0",
+    k_ij_t = "0.0",
+    temperature_dependent = "false",
+    l_ij = "0.0",
+    combining_rule = "None",
+    max_iter = "500",
+    tol = "1e-10",
+    feynman_hibbs = "false",
+    tail_correction = "false",
+    hard_sphere_model = "HardSphereModel::BMCSL",
+    hs_diameter = "HardSphereDiameter::Correlation"
+)]
+#[pyo3(
+    name = "fit_binary",
+    text_signature = "(pure_records, data, objective=None, k_ij=0.0, k_ij_t=0.0, temperature_dependent=False, l_ij=0.0, combining_rule=None, max_iter=500, tol=1e-10, feynman_hibbs=False, tail_correction=False, hard_sphere_model=HardSphereModel.BMCSL, hs_diameter=HardSphereDiameter.Correlation)"
+)]
+pub fn py_fit_binary(
+    pure_records: Vec<PyPureRecord>,
+    data: Vec<PyBinaryVle>,
+    objective: Option<BinaryVleObjective>,
+    k_ij: f64,
+    k_ij_t: f64,
+    temperature_dependent: bool,
+    l_ij: f64,
+    combining_rule: Option<CombiningRule>,
+    max_iter: usize,
+    tol: f64,
+    feynman_hibbs: bool,
+    tail_correction: bool,
+    hard_sphere_model: HardSphereModel,
+    hs_diameter: HardSphereDiameter,
+) -> PyResult<PyBinaryFitResult> {
+    let eos_options = PetsOptions {
+        feynman_hibbs,
+        tail_correction,
+        hard_sphere_model,
+        hs_diameter,
+        ..Default::default()
+    };
+    let options = FitOptions {
+        max_iter,
+        tol,
+        ..Default::default()
+    };
+    let binary_record = PetsBinaryRecord {
+        combining_rule,
+        ..PetsBinaryRecord::new(k_ij, k_ij_t, l_ij)
+    };
+    let pure_records: Vec<_> = pure_records.into_iter().map(|r| r.0).collect();
+    let data: Vec<_> = data.into_iter().map(|d| d.0).collect();
+    Ok(PyBinaryFitResult(fit_binary(
+        &pure_records,
+        &binary_record,
+        &data,
+        objective.unwrap_or(BinaryVleObjective::BubblePoint),
+        temperature_dependent,
+        eos_options,
+        options,
+    )?))
+}
+
+/// Experimental transport properties of a pure substance.
+///
+/// Either the pressures or the molar densities of the data points
+/// have to be given.
+///
+/// Parameters
+/// ----------
+/// property : TransportProperty
+///     The transport property.
+/// temperature : SIArray1
+///     Temperatures of the data points.
+/// value : SIArray1
+///     Viscosities, self-diffusion coefficients or thermal conductivities.
+/// pressure : SIArray1, optional
+///     Pressures of the data points.
+/// density : SIArray1, optional
+///     Molar densities of the data points.
+///
+/// Returns
+/// -------
+/// TransportData
+#[pyclass(name = "TransportData", unsendable)]
+#[pyo3(text_signature = "(property, temperature, value, pressure=None, density=None)")]
+#[derive(Clone)]
+pub struct PyTransportData(pub TransportData);
+
+#[pymethods]
+impl PyTransportData {
+    #[new]
+    fn new(
+        property: TransportProperty,
+        temperature: PySIArray1,
+        value: PySIArray1,
+        pressure: Option<PySIArray1>,
+        density: Option<PySIArray1>,
+    ) -> PyResult<Self> {
+        let data = match (pressure, density) {
+            (Some(p), None) => {
+                TransportData::new(property, temperature.into(), p.into(), value.into())?
+            }
+            (None, Some(rho)) => {
+                TransportData::new_density(property, temperature.into(), rho.into(), value.into())?
+            }
+            _ => {
+                return Err(PyValueError::new_err(
+                    "either pressure or density has to be given.",
+                ))
+            }
+        };
+        Ok(Self(data))
+    }
+}
+
+/// Result of the regression of entropy scaling coefficients.
+#[pyclass(name = "TransportFitResult", unsendable)]
+#[derive(Clone)]
+pub struct PyTransportFitResult(pub TransportFitResult);
+
+#[pymethods]
+impl PyTransportFitResult {
+    #[getter]
+    fn get_record(&self) -> PyPetsRecord {
+        PyPetsRecord(self.0.record.clone())
+    }
+
+    #[getter]
+    fn get_relative_deviation<'py>(&self, py: Python<'py>) -> &'py PyArray1<f64> {
+        self.0.relative_deviation.view().to_pyarray(py)
+    }
+
+    #[getter]
+    fn get_aad(&self) -> f64 {
+        self.0.aad
+    }
+
+    #[getter]
+    fn get_max_deviation(&self) -> f64 {
+        self.0.max_deviation
+    }
+}
+
+/// Fit the entropy scaling coefficients of a pure substance
+/// to experimental transport properties.
+///
+/// The options of the equation of state have to be the options of the
+/// equation of state that the fitted coefficients are used with.
+///
+/// Parameters
+/// ----------
+/// pure_record : PureRecord
+///     Pure substance record with the PeTS parameters.
+/// data : TransportData
+///     Experimental transport properties.
+/// feynman_hibbs : bool, optional
+///     Apply the Feynman-Hibbs quantum correction. Defaults to False.
+/// tail_correction : bool, optional
+///     Truncate the potential at 2.5 sigma without shifting and add the
+///     long-range correction. Defaults to False.
+/// hard_sphere_model : HardSphereModel, optional
+///     Hard-sphere mixture reference. Defaults to HardSphereModel.BMCSL.
+/// hs_diameter : HardSphereDiameter, optional
+///     Model of the hard-sphere diameter. Defaults to HardSphereDiameter.Correlation.
+/// ideal_gas : IdealGasModel, optional
+///     Model of the ideal gas contribution. Defaults to IdealGasModel.Automatic,
+///     the ideal gas records if available and the QSPR model otherwise.
+///
+/// Returns
+/// -------
+/// TransportFitResult
+#[pyfunction(
+    feynman_hibbs = "false",
+    tail_correction = "false",
+    hard_sphere_model = "HardSphereModel::BMCSL",
+    hs_diameter = "HardSphereDiameter::Correlation",
+    ideal_gas = "IdealGasModel::Automatic"
+)]
+#[pyo3(
+    name = "fit_entropy_scaling",
+    text_signature = "(pure_record, data, feynman_hibbs=False, tail_correction=False, hard_sphere_model=HardSphereModel.BMCSL, hs_diameter=HardSphereDiameter.Correlation, ideal_gas=IdealGasModel.Automatic)"
+)]
+pub fn py_fit_entropy_scaling(
+    pure_record: PyPureRecord,
+    data: PyTransportData,
+    feynman_hibbs: bool,
+    tail_correction: bool,
+    hard_sphere_model: HardSphereModel,
+    hs_diameter: HardSphereDiameter,
+    ideal_gas: IdealGasModel,
+) -> PyResult<PyTransportFitResult> {
+    let options = PetsOptions {
+        feynman_hibbs,
+        tail_correction,
+        hard_sphere_model,
+        hs_diameter,
+        ideal_gas,
+        ..Default::default()
+    };
+    Ok(PyTransportFitResult(fit_entropy_scaling(
+        &pure_record.0,
+        &data.0,
+        options,
+    )?))
+}
@@ -0,0 +1,326 @@
+//! Lennard-Jones reduced units for pure PeTS fluids.
+//!
+//! Reduced properties are obtained by scaling with the segment
+//! diameter `sigma`, the energy parameter `epsilon` and the mass
+//! of a molecule, e.g. T* = kT/epsilon or rho* = rho sigma^3.
+use crate::parameters::PetsParameters;
+use feos_core::{Contributions, EntropyScaling, EosError, EosResult, EquationOfState, State};
+use feos_dft::interface::PlanarInterface;
+use feos_dft::HelmholtzEnergyFunctional;
+use ndarray::arr1;
+use quantity::si::*;
+use std::rc::Rc;
+
+/// Reference quantities of the reduced units of a pure component.
+#[derive(Clone, Copy, Debug)]
+pub struct ReducedUnits {
+    /// Segment diameter
+    pub sigma: SINumber,
+    /// Energy parameter
+    pub epsilon: SINumber,
+    /// Mass of a molecule
+    pub mass: SINumber,
+}
+
+impl ReducedUnits {
+    /// Reduced units defined by the parameters of the given component.
+    pub fn new(parameters: &PetsParameters, component: usize) -> Self {
+        Self {
+            sigma: parameters.sigma[component] * ANGSTROM,
+            epsilon: parameters.epsilon_k[component] * KELVIN * KB,
+            mass: parameters.molarweight[component] * GRAM / MOL / NAV,
+        }
+    }
+
+    pub fn temperature(&self) -> SINumber {
+        self.epsilon / KB
+    }
+
+    /// Reference for molar densities.
+    pub fn density(&self) -> SINumber {
+        1.0 / (self.sigma.powi(3) * NAV)
+    }
+
+    pub fn pressure(&self) -> SINumber {
+        self.epsilon / self.sigma.powi(3)
+    }
+
+    /// Reference for molar energies (including the chemical potential).
+    pub fn molar_energy(&self) -> SINumber {
+        self.epsilon * NAV
+    }
+
+    pub fn surface_tension(&self) -> SINumber {
+        self.epsilon / self.sigma.powi(2)
+    }
+
+    /// Reduced surface tension of a solved planar interface.
+    pub fn reduced_surface_tension<F: HelmholtzEnergyFunctional>(
+        &self,
+        interface: &PlanarInterface<SIUnit, F>,
+    ) -> EosResult<f64> {
+        let surface_tension = interface.surface_tension.ok_or_else(|| {
+            EosError::UndeterminedState(String::from(
+                "the surface tension of the planar interface is not calculated",
+            ))
+        })?;
+        Ok(surface_tension.to_reduced(self.surface_tension())?)
+    }
+
+    pub fn time(&self) -> SINumber {
+        self.sigma * (self.mass / self.epsilon).sqrt().unwrap()
+    }
+
+    pub fn viscosity(&self) -> SINumber {
+        self.epsilon * self.time() / self.sigma.powi(3)
+    }
+
+    pub fn diffusion(&self) -> SINumber {
+        self.sigma.powi(2) / self.time()
+    }
+
+    pub fn thermal_conductivity(&self) -> SINumber {
+        KB / (self.sigma * self.time())
+    }
+}
+
+/// Thermodynamic state of a pure fluid specified and evaluated in reduced units.
+pub struct ReducedState<E> {
+    pub state: State<SIUnit, E>,
+    pub units: ReducedUnits,
+}
+
+impl<E: EquationOfState> ReducedState<E> {
+    /// Create a state from the reduced temperature and the reduced density.
+    pub fn new(
+        eos: &Rc<E>,
+        units: ReducedUnits,
+        temperature: f64,
+        density: f64,
+    ) -> EosResult<Self> {
+        if eos.components() != 1 {
+            return Err(EosError::IncompatibleComponents(eos.components(), 1));
+        }
+        let moles = arr1(&[1.0]) * MOL;
+        let state = State::new_nvt(
+            eos,
+            temperature * units.temperature(),
+            moles.sum() / (density * units.density()),
+            &moles,
+        )?;
+        Ok(Self { state, units })
+    }
+
+    pub fn temperature(&self) -> f64 {
+        self.state
+            .temperature
+            .to_reduced(self.units.temperature())
+            .unwrap()
+    }
+
+    pub fn density(&self) -> f64 {
+        self.state.density.to_reduced(self.units.density()).unwrap()
+    }
+
+    pub fn pressure(&self, contributions: Contributions) -> f64 {
+        self.state
+            .pressure(contributions)
+            .to_reduced(self.units.pressure())
+            .unwrap()
+    }
+
+    /// Internal energy per molecule.
+    pub fn internal_energy(&self, contributions: Contributions) -> f64 {
+        self.state
+            .molar_internal_energy(contributions)
+            .to_reduced(self.units.molar_energy())
+            .unwrap()
+    }
+
+    pub fn chemical_potential(&self, contributions: Contributions) -> f64 {
+        self.state
+            .chemical_potential(contributions)
+            .get(0)
+            .to_reduced(self.units.molar_energy())
+            .unwrap()
+    }
+}
+
+impl<E: EquationOfState + EntropyScaling<SIUnit>> ReducedState<E> {
+    pub fn viscosity(&self) -> EosResult<f64> {
+        Ok(self.state.viscosity()?.to_reduced(self.units.viscosity())?)
+    }
+
+    pub fn diffusion(&self) -> EosResult<f64> {
+        Ok(self.state.diffusion()?.to_reduced(self.units.diffusion())?)
+    }
+
+    pub fn thermal_conductivity(&self) -> EosResult<f64> {
+        Ok(self
+            .state
+            .thermal_conductivity()?
+            .to_reduced(self.units.thermal_conductivity())?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eos::Pets;
+    use crate::parameters::utils::argon_parameters;
+    use crate::PetsFunctional;
+    use approx::assert_relative_eq;
+    use feos_core::{PhaseEquilibrium, SolverOptions};
+
+    #[test]
+    fn round_trip() {
+        let parameters = argon_parameters();
+        let units = ReducedUnits::new(&parameters, 0);
+        let eos = Rc::new(Pets::new(parameters));
+        let s = ReducedState::new(&eos, units, 1.2, 0.6).unwrap();
+        assert_relative_eq!(s.temperature(), 1.2, max_relative = 1e-14);
+        assert_relative_eq!(s.density(), 0.6, max_relative = 1e-14);
+
+        let sigma = 3.405e-10;
+        let epsilon = 119.8 * 1.380649e-23;
+        let mass = 39.948e-3 / 6.02214076e23;
+        let state = &s.state;
+        assert_relative_eq!(
+            state.temperature,
+            1.2 * 119.8 * KELVIN,
+            max_relative = 1e-14
+        );
+        assert_relative_eq!(
+            (state.density * NAV * (sigma * METER).powi(3))
+                .into_value()
+                .unwrap(),
+            0.6,
+            max_relative = 1e-14
+        );
+        let c = Contributions::Total;
+        assert_relative_eq!(
+            s.pressure(c),
+            state.pressure(c).to_reduced(PASCAL).unwrap() * sigma.powi(3) / epsilon,
+            max_relative = 1e-12
+        );
+        assert_relative_eq!(
+            s.internal_energy(c),
+            state
+                .molar_internal_energy(c)
+                .to_reduced(JOULE / MOL)
+                .unwrap()
+                / (epsilon * 6.02214076e23),
+            max_relative = 1e-12
+        );
+        assert_relative_eq!(
+            s.viscosity().unwrap(),
+            state
+                .viscosity()
+                .unwrap()
+                .to_reduced(PASCAL * SECOND)
+                .unwrap()
+                * sigma.powi(2)
+                / (mass * epsilon).sqrt(),
+            max_relative = 1e-12
+        );
+        assert_relative_eq!(
+            s.diffusion().unwrap(),
+            state
+                .diffusion()
+                .unwrap()
+                .to_reduced(METER.powi(2) / SECOND)
+                .unwrap()
+                * (mass / epsilon).sqrt()
+                / sigma,
+            max_relative = 1e-12
+        );
+        assert_relative_eq!(
+            s.thermal_conductivity().unwrap(),
+            state
+                .thermal_conductivity()
+                .unwrap()
+                .to_reduced(WATT / METER / KELVIN)
+                .unwrap()
+                * sigma.powi(2)
+                / 1.380649e-23
+                * (mass / epsilon).sqrt(),
+            max_relative = 1e-12
+        );
+    }
+
+    #[test]
+    fn corresponding_states() {
+        let argon = argon_parameters();
+        let unit_fluid = Rc::new(PetsParameters::unit_fluid());
+        let s1 = ReducedState::new(
+            &Rc::new(Pets::new(argon.clone())),
+            ReducedUnits::new(&argon, 0),
+            1.2,
+            0.6,
+        )
+        .unwrap();
+        let s2 = ReducedState::new(
+            &Rc::new(Pets::new(unit_fluid.clone())),
+            ReducedUnits::new(&unit_fluid, 0),
+            1.2,
+            0.6,
+        )
+        .unwrap();
+        let c = Contributions::ResidualNvt;
+        assert_relative_eq!(s1.pressure(c), s2.pressure(c), max_relative = 1e-12);
+        assert_relative_eq!(
+            s1.internal_energy(c),
+            s2.internal_energy(c),
+            max_relative = 1e-12
+        );
+        assert_relative_eq!(
+            s1.chemical_potential(c),
+            s2.chemical_potential(c),
+            max_relative = 1e-12
+        );
+    }
+
+    #[test]
+    fn surface_tension() {
+        let surface_tension = |parameters: Rc<PetsParameters>| {
+            let units = ReducedUnits::new(&parameters, 0);
+            let func = Rc::new(PetsFunctional::new(parameters));
+            let vle = PhaseEquilibrium::pure(
+                &func,
+                0.8 * units.temperature(),
+                None,
+                SolverOptions::default(),
+            )
+            .unwrap();
+            let interface = PlanarInterface::from_tanh(
+                &vle,
+                256,
+                40.0 * units.sigma,
+                1.1 * units.temperature(),
+            )
+            .unwrap();
+            // the surface tension is only available after solving the profile
+            assert!(units.reduced_surface_tension(&interface).is_err());
+            let interface = interface.solve(None).unwrap();
+            let gamma = units.reduced_surface_tension(&interface).unwrap();
+            assert_relative_eq!(
+                gamma,
+                interface
+                    .surface_tension
+                    .unwrap()
+                    .to_reduced(units.surface_tension())
+                    .unwrap(),
+                max_relative = 1e-12
+            );
+            gamma
+        };
+        let gamma = surface_tension(argon_parameters());
+        assert_relative_eq!(
+            gamma,
+            surface_tension(Rc::new(PetsParameters::unit_fluid())),
+            max_relative = 1e-6
+        );
+        // positive and of the order of magnitude of the Lennard-Jones fluid
+        assert!(gamma > 0.1 && gamma < 1.0);
+    }
+}